@@ -1,12 +1,33 @@
+use crate::bandits::bandit::Bandit;
+use rand::{Rng, RngCore};
 use rand_distr::Distribution;
-use rand_distr::Normal;
+use rand_distr::{Exp, Normal, Poisson};
+use std::cell::Cell;
+use std::f64::consts::PI;
 
+/// Represents an arm of a bandit problem.
+///
+/// Arms sample their rewards from the random number generator given to
+/// [`Arm::pull_with`], so that seeded generators make the rewards reproducible, while
+/// [`Arm::pull`] uses the thread-local generator. Custom arms written against the former
+/// `pull(&self)` method should implement `pull_with` instead, sampling from the given
+/// generator in place of `rand::thread_rng()`. Calls to `pull` remain unchanged.
 pub trait Arm {
     /// Return the true value of the arm or none if the value is unknown.
     fn value(&self) -> Option<f64>;
 
-    /// Pulling the arm should yield a reward.
-    fn pull(&self) -> f64;
+    /// Pulling the arm should yield a reward, sampled using the given random number generator.
+    fn pull_with(&self, rng: &mut dyn RngCore) -> f64;
+
+    /// Pulling the arm should yield a reward, sampled using the thread-local generator.
+    fn pull(&self) -> f64 {
+        self.pull_with(&mut rand::thread_rng())
+    }
+
+    /// Resets the arm to its initial state. Arms whose rewards depend on the previous pulls,
+    /// e.g., seasonal, drifting or scripted arms, should start over, while stationary arms
+    /// have nothing to reset. Benchmarks reset the arms before every bandit of every run.
+    fn reset(&self) {}
 }
 
 /// Random arms sample rewards from an underlying reward distribution. The assumption is that
@@ -57,16 +78,332 @@ impl RandomArm<Normal<f64>> {
     }
 }
 
+impl RandomArm<Poisson<f64>> {
+    /// A Poisson arm yields non-negative integer rewards, e.g., the number of arrivals per
+    /// time unit, following a Poisson distribution whose mean is the true value of the arm.
+    ///
+    /// - `lambda` - mean of the reward distribution and the true value of the arm.
+    ///
+    /// # Example
+    ///```
+    /// use readapt::bandits::arm::{Arm, RandomArm};
+    ///
+    /// let arm = RandomArm::poisson(3.0);
+    /// assert_eq!(arm.value(), Some(3.0));
+    /// println!("Pulling the arm! Received reward: {}", arm.pull())
+    ///```
+    pub fn poisson(lambda: f64) -> Self {
+        RandomArm {
+            value: Some(lambda),
+            reward_distribution: Poisson::new(lambda)
+                .unwrap_or_else(|_| panic!("Invalid lambda value: {lambda}")),
+        }
+    }
+}
+
+impl RandomArm<Exp<f64>> {
+    /// An exponential arm yields non-negative rewards, e.g., waiting times, following an
+    /// exponential distribution whose mean `1 / rate` is the true value of the arm.
+    ///
+    /// - `rate` - the rate of the reward distribution, which must be positive.
+    ///
+    /// # Example
+    ///```
+    /// use readapt::bandits::arm::{Arm, RandomArm};
+    ///
+    /// let arm = RandomArm::exponential(2.0);
+    /// assert_eq!(arm.value(), Some(0.5));
+    /// println!("Pulling the arm! Received reward: {}", arm.pull())
+    ///```
+    pub fn exponential(rate: f64) -> Self {
+        if !(rate > 0.0 && rate.is_finite()) {
+            panic!("Invalid rate value: {rate}");
+        }
+
+        RandomArm {
+            value: Some(1.0 / rate),
+            reward_distribution: Exp::new(rate).unwrap(),
+        }
+    }
+}
+
 impl<D: Distribution<f64>> Arm for RandomArm<D> {
     fn value(&self) -> Option<f64> {
         self.value
     }
 
-    fn pull(&self) -> f64 {
-        self.reward_distribution.sample(&mut rand::thread_rng())
+    fn pull_with(&self, rng: &mut dyn RngCore) -> f64 {
+        self.reward_distribution.sample(rng)
+    }
+}
+
+/// Bernoulli arms yield a reward of one with probability `p` and zero otherwise. They model
+/// binary outcomes, such as clicks or conversions, and their true value is the success
+/// probability.
+#[derive(Clone, Debug)]
+pub struct BernoulliArm {
+    p: f64,
+}
+
+impl BernoulliArm {
+    /// Creates a Bernoulli arm.
+    ///
+    /// - `p` - the probability of success, between 0 and 1.
+    ///
+    /// # Example
+    ///```
+    /// use readapt::bandits::arm::{Arm, BernoulliArm};
+    ///
+    /// // the following arm yields a reward of one 10% of the time
+    /// let arm = BernoulliArm::new(0.1);
+    /// assert_eq!(arm.value(), Some(0.1));
+    /// println!("Pulling the arm! Received reward: {}", arm.pull())
+    ///```
+    pub fn new(p: f64) -> Self {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("Invalid p value: {p}");
+        }
+
+        BernoulliArm { p }
+    }
+}
+
+impl Arm for BernoulliArm {
+    fn value(&self) -> Option<f64> {
+        Some(self.p)
+    }
+
+    fn pull_with(&self, rng: &mut dyn RngCore) -> f64 {
+        if rng.gen_bool(self.p) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Seasonal arms have a mean that follows a sinusoid over the number of pulls, that is,
+/// `mean(t) = base + amplitude * sin(2π t / period)`, and yield normally distributed rewards
+/// around the current mean. They model periodic non-stationarity, where the value of the arm
+/// rises and falls in cycles.
+///
+/// Note that the arm keeps track of its pull count using interior mutability (a `Cell`), so
+/// that pulling through a shared reference, as required by the `Arm` trait, advances the
+/// season. As a consequence, the arm is not `Sync` and should not be shared across threads.
+#[derive(Clone, Debug)]
+pub struct SeasonalArm {
+    base: f64,
+    amplitude: f64,
+    period: usize,
+    reward_distribution: Normal<f64>,
+    pulls: Cell<usize>,
+}
+
+impl SeasonalArm {
+    /// Creates a seasonal arm.
+    ///
+    /// - `base` - the mean around which the arm value oscillates.
+    /// - `amplitude` - the maximum deviation of the mean from the base.
+    /// - `period` - the number of pulls needed to complete a full cycle.
+    /// - `reward_sigma` - the standard deviation of the rewards around the current mean.
+    ///
+    /// # Example
+    ///```
+    /// use readapt::bandits::arm::{Arm, SeasonalArm};
+    ///
+    /// // the mean of the arm starts at 1 and oscillates between 0 and 2 every 100 pulls
+    /// let arm = SeasonalArm::new(1.0, 1.0, 100, 0.1);
+    /// assert_eq!(arm.value(), Some(1.0));
+    /// println!("Pulling the arm! Received reward: {}", arm.pull())
+    ///```
+    pub fn new(base: f64, amplitude: f64, period: usize, reward_sigma: f64) -> Self {
+        if period == 0 {
+            panic!("Invalid period value: {period}");
+        }
+
+        SeasonalArm {
+            base,
+            amplitude,
+            period,
+            reward_distribution: Normal::new(0.0, reward_sigma)
+                .unwrap_or_else(|_| panic!("Invalid sigma value: {reward_sigma}")),
+            pulls: Cell::new(0),
+        }
+    }
+
+    /// Returns the mean of the arm after the given number of pulls.
+    fn mean(&self, t: usize) -> f64 {
+        self.base + self.amplitude * f64::sin(2.0 * PI * t as f64 / self.period as f64)
+    }
+}
+
+impl Arm for SeasonalArm {
+    fn value(&self) -> Option<f64> {
+        Some(self.mean(self.pulls.get()))
+    }
+
+    fn pull_with(&self, rng: &mut dyn RngCore) -> f64 {
+        let t = self.pulls.get();
+        self.pulls.set(t + 1);
+        self.mean(t) + self.reward_distribution.sample(rng)
+    }
+
+    /// Restarts the season from the first pull.
+    fn reset(&self) {
+        self.pulls.set(0);
+    }
+}
+
+/// Drifting arms have a mean that performs a Gaussian random walk, that is, after each pull
+/// the mean is incremented by a sample of `Normal(0, drift_sigma)`. They yield normally
+/// distributed rewards around the current mean and model non-stationary problems, which
+/// bandits having a constant learning rate are able to track.
+///
+/// Note that, similar to seasonal arms, the current mean is kept in a `Cell`, so that pulling
+/// through a shared reference moves the mean. Thus, the arm is not `Sync`.
+#[derive(Clone, Debug)]
+pub struct DriftingArm {
+    initial_value: f64,
+    mean: Cell<f64>,
+    drift_distribution: Normal<f64>,
+    reward_distribution: Normal<f64>,
+}
+
+impl DriftingArm {
+    /// Creates a drifting arm.
+    ///
+    /// - `initial_value` - the mean of the arm before the first pull.
+    /// - `drift_sigma` - the standard deviation of the mean increment after each pull.
+    /// - `reward_sigma` - the standard deviation of the rewards around the current mean.
+    ///
+    /// # Example
+    ///```
+    /// use readapt::bandits::arm::{Arm, DriftingArm};
+    ///
+    /// let arm = DriftingArm::new(0.0, 0.01, 1.0);
+    /// assert_eq!(arm.value(), Some(0.0));
+    /// println!("Pulling the arm! Received reward: {}", arm.pull())
+    ///```
+    pub fn new(initial_value: f64, drift_sigma: f64, reward_sigma: f64) -> Self {
+        for sigma in [drift_sigma, reward_sigma] {
+            if !(sigma >= 0.0 && sigma.is_finite()) {
+                panic!("Invalid sigma value: {sigma}");
+            }
+        }
+
+        DriftingArm {
+            initial_value,
+            mean: Cell::new(initial_value),
+            drift_distribution: Normal::new(0.0, drift_sigma).unwrap(),
+            reward_distribution: Normal::new(0.0, reward_sigma).unwrap(),
+        }
+    }
+}
+
+impl Arm for DriftingArm {
+    fn value(&self) -> Option<f64> {
+        Some(self.mean.get())
+    }
+
+    fn pull_with(&self, rng: &mut dyn RngCore) -> f64 {
+        let mean = self.mean.get();
+        self.mean.set(mean + self.drift_distribution.sample(rng));
+        mean + self.reward_distribution.sample(rng)
+    }
+
+    /// Moves the mean back to the initial value.
+    fn reset(&self) {
+        self.mean.set(self.initial_value);
+    }
+}
+
+/// Determines what a scripted arm yields once its rewards are exhausted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScriptEnd {
+    /// Starts over from the first reward.
+    Wrap,
+    /// Keeps yielding the last reward.
+    Clamp,
+}
+
+/// Scripted arms yield a predefined sequence of rewards, one per pull, regardless of the
+/// random number generator. They make the rewards received by a bandit exactly known, which
+/// is useful for testing, and can encode adversarial reward sequences. The true value of the
+/// arm is the reward of the next pull.
+///
+/// Note that, similar to seasonal arms, the position in the sequence is kept in a `Cell`, so
+/// that pulling through a shared reference advances it. Thus, the arm is not `Sync`.
+#[derive(Clone, Debug)]
+pub struct ScriptedArm {
+    rewards: Vec<f64>,
+    end: ScriptEnd,
+    cursor: Cell<usize>,
+}
+
+impl ScriptedArm {
+    /// Creates a scripted arm.
+    ///
+    /// - `rewards` - the sequence of rewards, which must not be empty.
+    /// - `end` - what the arm yields once the rewards are exhausted.
+    ///
+    /// # Example
+    ///```
+    /// use readapt::bandits::arm::{Arm, ScriptEnd, ScriptedArm};
+    ///
+    /// let arm = ScriptedArm::new(vec![1.0, 0.0], ScriptEnd::Wrap);
+    /// assert_eq!(arm.pull(), 1.0);
+    /// assert_eq!(arm.pull(), 0.0);
+    /// assert_eq!(arm.pull(), 1.0);
+    ///```
+    pub fn new(rewards: Vec<f64>, end: ScriptEnd) -> Self {
+        if rewards.is_empty() {
+            panic!("Scripted rewards must not be empty");
+        }
+
+        ScriptedArm {
+            rewards,
+            end,
+            cursor: Cell::new(0),
+        }
+    }
+
+    /// Returns the index of the reward yielded by the next pull.
+    fn position(&self) -> usize {
+        match self.end {
+            ScriptEnd::Wrap => self.cursor.get() % self.rewards.len(),
+            ScriptEnd::Clamp => self.cursor.get().min(self.rewards.len() - 1),
+        }
     }
 }
 
+impl Arm for ScriptedArm {
+    fn value(&self) -> Option<f64> {
+        Some(self.rewards[self.position()])
+    }
+
+    fn pull_with(&self, _: &mut dyn RngCore) -> f64 {
+        let reward = self.rewards[self.position()];
+        self.cursor.set(self.cursor.get() + 1);
+        reward
+    }
+
+    /// Starts over from the first reward.
+    fn reset(&self) {
+        self.cursor.set(0);
+    }
+}
+
+/// Represents the outcome of running a single bandit against a multi-arm.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BanditEpisode {
+    /// The arm selected at each step.
+    pub arms: Vec<usize>,
+    /// The reward received at each step.
+    pub rewards: Vec<f64>,
+    /// The sum of all rewards received.
+    pub total_reward: f64,
+}
+
 #[derive(Clone, Debug)]
 pub struct MultiArm<A: Arm> {
     arms: Vec<A>,
@@ -81,23 +418,135 @@ impl<A: Arm> MultiArm<A> {
         self.arms[k].pull()
     }
 
+    /// Resets every arm to its initial state, see [`Arm::reset`].
+    pub fn reset(&self) {
+        self.arms.iter().for_each(Arm::reset);
+    }
+
+    /// Pulls the k-th arm, sampling the reward using the given random number generator.
+    ///
+    /// - `k` - the index of the arm to pull.
+    /// - `rng` - the random number generator.
+    pub fn pull_with(&self, k: usize, rng: &mut dyn RngCore) -> f64 {
+        self.arms[k].pull_with(rng)
+    }
+
+    /// Replaces the k-th arm and returns the previous one. Bandits are not informed of the
+    /// change, so their estimates for the arm become stale.
+    ///
+    /// - `k` - the index of the arm to replace.
+    /// - `arm` - the new arm.
+    pub fn set_arm(&mut self, k: usize, arm: A) -> A {
+        std::mem::replace(&mut self.arms[k], arm)
+    }
+
+    /// Appends an arm, which gets the next index. Bandits are not informed of the change and
+    /// their number of arms is fixed at construction, so a new bandit having [`MultiArm::len`]
+    /// arms should be created, since restarting a bandit keeps its number of arms.
+    ///
+    /// - `arm` - the new arm.
+    pub fn add_arm(&mut self, arm: A) {
+        self.arms.push(arm);
+    }
+
+    /// Removes the k-th arm and returns it, shifting the indices of all subsequent arms down by
+    /// one. As for [`MultiArm::add_arm`], a new bandit having [`MultiArm::len`] arms should be
+    /// created after the removal. It panics if `k` is out of bounds.
+    ///
+    /// - `k` - the index of the arm to remove.
+    pub fn remove_arm(&mut self, k: usize) -> A {
+        self.arms.remove(k)
+    }
+
+    /// Returns the number of arms.
+    pub fn len(&self) -> usize {
+        self.arms.len()
+    }
+
+    /// Returns true if there are no arms.
+    pub fn is_empty(&self) -> bool {
+        self.arms.is_empty()
+    }
+
+    /// Runs a bandit against the arms for a number of steps and returns the episode. The bandit
+    /// is neither restarted before nor after the run, so consecutive runs continue learning.
+    /// In contrast to a benchmark, a single run is not averaged, which is useful for inspecting
+    /// the behavior of a bandit step by step.
+    ///
+    /// - `bandit` - the bandit selecting the arms.
+    /// - `steps` - the number of steps.
+    /// - `rng` - the random number generator used to sample the rewards.
+    pub fn run(
+        &self,
+        bandit: &mut dyn Bandit,
+        steps: usize,
+        rng: &mut dyn RngCore,
+    ) -> BanditEpisode {
+        let mut arms = Vec::with_capacity(steps);
+        let mut rewards = Vec::with_capacity(steps);
+
+        for _ in 0..steps {
+            let arm = bandit.select_arm();
+            let reward = self.pull_with(arm, rng);
+            bandit.receive_reward(reward);
+            arms.push(arm);
+            rewards.push(reward);
+        }
+
+        BanditEpisode {
+            arms,
+            total_reward: rewards.iter().sum(),
+            rewards,
+        }
+    }
+
+    /// Returns the true value of the optimal arm, or none if the value of any arm is unknown.
+    pub fn optimal_value(&self) -> Option<f64> {
+        self.optimal_arm().and_then(|k| self.arms[k].value())
+    }
+
     pub fn optimal_arm(&self) -> Option<usize> {
-        if self.arms.iter().any(|arm| arm.value().is_none()) {
-            None
-        } else {
-            self.arms
+        self.values().and_then(|values| {
+            values
                 .iter()
                 .enumerate()
-                .max_by(|(_, a), (_, b)| a.value().unwrap().total_cmp(&b.value().unwrap()))
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
                 .map(|(index, _)| index)
-        }
+        })
+    }
+
+    /// Returns the true value of each arm, e.g., for computing the regret of a bandit, or none
+    /// if the value of any arm is unknown.
+    pub fn values(&self) -> Option<Vec<f64>> {
+        self.arms.iter().map(|arm| arm.value()).collect()
+    }
+}
+
+impl MultiArm<RandomArm<Normal<f64>>> {
+    /// Creates a multi-arm whose arms always yield their true value, that is, normal arms
+    /// having zero variance. Deterministic arms make the rewards received by a bandit exactly
+    /// predictable, which is useful for testing.
+    ///
+    /// - `values` - the true value of each arm.
+    pub fn deterministic(values: &[f64]) -> Self {
+        MultiArm::new(
+            values
+                .iter()
+                .map(|&value| {
+                    RandomArm::from_distribution(Some(value), Normal::new(value, 0.0).unwrap())
+                })
+                .collect(),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bandits::bandit::StochasticBandit;
     use rand::distributions::Uniform;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
     fn standard_normal_arm() {
@@ -118,6 +567,142 @@ mod tests {
         assert!((0f64..1f64).contains(&reward));
     }
 
+    #[test]
+    fn seeded_pulls() {
+        let arm = RandomArm::normal(0.0);
+        let (mut rng, mut other_rng) = (StdRng::seed_from_u64(7), StdRng::seed_from_u64(7));
+        for _ in 0..10 {
+            assert_eq!(arm.pull_with(&mut rng), arm.pull_with(&mut other_rng));
+        }
+    }
+
+    #[test]
+    fn scripted_arm() {
+        let arm = ScriptedArm::new(vec![1.0, 2.0, 3.0], ScriptEnd::Wrap);
+        let rewards: Vec<f64> = (0..5).map(|_| arm.pull()).collect();
+        assert_eq!(rewards, [1.0, 2.0, 3.0, 1.0, 2.0]);
+        assert_eq!(arm.value(), Some(3.0));
+
+        let arm = ScriptedArm::new(vec![1.0, 2.0, 3.0], ScriptEnd::Clamp);
+        let rewards: Vec<f64> = (0..5).map(|_| arm.pull()).collect();
+        assert_eq!(rewards, [1.0, 2.0, 3.0, 3.0, 3.0]);
+
+        // resetting starts over from the first reward
+        arm.reset();
+        assert_eq!(arm.pull(), 1.0);
+
+        // the sample averages of a greedy bandit follow the known reward stream
+        let multi_arm = MultiArm::new(vec![ScriptedArm::new(vec![4.0, 2.0, 0.0], ScriptEnd::Wrap)]);
+        let mut bandit = StochasticBandit::greedy(1);
+        let mut rng = StdRng::seed_from_u64(0);
+        multi_arm.run(&mut bandit, 1, &mut rng);
+        assert_eq!(bandit.estimated_values(), &[4.0]);
+        multi_arm.run(&mut bandit, 2, &mut rng);
+        assert_eq!(bandit.estimated_values(), &[2.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Scripted rewards must not be empty")]
+    fn empty_scripted_arm() {
+        ScriptedArm::new(Vec::new(), ScriptEnd::Clamp);
+    }
+
+    #[test]
+    fn poisson_arm() {
+        let arm = RandomArm::poisson(4.0);
+        assert_eq!(arm.value(), Some(4.0));
+
+        let rewards: Vec<f64> = (0..1000).map(|_| arm.pull()).collect();
+        assert!(rewards.iter().all(|&r| r >= 0.0 && r.fract() == 0.0));
+        let mean = rewards.iter().sum::<f64>() / 1000.0;
+        assert!((mean - 4.0).abs() < 0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid lambda value: 0")]
+    fn invalid_poisson_arm() {
+        RandomArm::poisson(0.0);
+    }
+
+    #[test]
+    fn exponential_arm() {
+        let arm = RandomArm::exponential(2.0);
+        assert_eq!(arm.value(), Some(0.5));
+
+        let rewards: Vec<f64> = (0..1000).map(|_| arm.pull()).collect();
+        assert!(rewards.iter().all(|&r| r >= 0.0));
+        let mean = rewards.iter().sum::<f64>() / 1000.0;
+        assert!((mean - 0.5).abs() < 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid rate value: -1")]
+    fn invalid_exponential_arm() {
+        RandomArm::exponential(-1.0);
+    }
+
+    #[test]
+    fn bernoulli_arm() {
+        assert_eq!(BernoulliArm::new(0.0).pull(), 0.0);
+        assert_eq!(BernoulliArm::new(1.0).pull(), 1.0);
+
+        let arm = BernoulliArm::new(0.3);
+        assert_eq!(arm.value(), Some(0.3));
+        let successes: f64 = (0..1000).map(|_| arm.pull()).sum();
+        assert!(successes > 200.0 && successes < 400.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid p value: 1.5")]
+    fn invalid_bernoulli_arm() {
+        BernoulliArm::new(1.5);
+    }
+
+    #[test]
+    fn seasonal_arm() {
+        let arm = SeasonalArm::new(1.0, 2.0, 4, 0.0);
+
+        // the mean follows the sinusoid 1 + 2 * sin(π t / 2)
+        for expected in [1.0, 3.0, 1.0, -1.0, 1.0] {
+            assert!((arm.value().unwrap() - expected).abs() < 1e-9);
+            assert!((arm.pull() - expected).abs() < 1e-9);
+        }
+        assert_eq!(arm.pulls.get(), 5);
+
+        arm.reset();
+        assert!((arm.value().unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid period value: 0")]
+    fn zero_period_seasonal_arm() {
+        SeasonalArm::new(1.0, 1.0, 0, 1.0);
+    }
+
+    #[test]
+    fn drifting_arm() {
+        let arm = DriftingArm::new(1.0, 0.0, 0.0);
+        assert_eq!(arm.pull(), 1.0);
+        assert_eq!(arm.value(), Some(1.0));
+
+        // the reward is sampled around the mean before the mean drifts
+        let arm = DriftingArm::new(1.0, 1.0, 0.0);
+        for _ in 0..10 {
+            let mean = arm.value().unwrap();
+            assert_eq!(arm.pull(), mean);
+        }
+        assert_ne!(arm.value(), Some(1.0));
+
+        arm.reset();
+        assert_eq!(arm.value(), Some(1.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid sigma value: -1")]
+    fn negative_drift_arm() {
+        DriftingArm::new(0.0, -1.0, 1.0);
+    }
+
     #[test]
     fn optimal_arm() {
         let arms = vec![
@@ -129,6 +714,8 @@ mod tests {
         let multi_arm = MultiArm::new(arms);
 
         assert_eq!(multi_arm.optimal_arm(), None);
+        assert_eq!(multi_arm.optimal_value(), None);
+        assert_eq!(multi_arm.values(), None);
 
         let arms = vec![
             RandomArm::from_distribution(Some(1f64), Uniform::new(0.0, 1.0)),
@@ -139,5 +726,54 @@ mod tests {
         let multi_arm = MultiArm::new(arms);
 
         assert_eq!(multi_arm.optimal_arm(), Some(1));
+        assert_eq!(multi_arm.optimal_value(), Some(5.0));
+        assert_eq!(multi_arm.values(), Some(vec![1.0, 5.0, 0.5]));
+    }
+
+    #[test]
+    fn mutate_arms() {
+        let mut multi_arm = MultiArm::deterministic(&[1.0, 2.0]);
+        assert_eq!(multi_arm.len(), 2);
+        assert_eq!(multi_arm.optimal_arm(), Some(1));
+
+        // a clearly superior arm becomes the optimal one
+        multi_arm.add_arm(RandomArm::from_distribution(
+            Some(10.0),
+            Normal::new(10.0, 0.0).unwrap(),
+        ));
+        assert_eq!(multi_arm.len(), 3);
+        assert_eq!(multi_arm.optimal_arm(), Some(2));
+
+        // removing an arm shifts the subsequent indices
+        assert_eq!(multi_arm.remove_arm(0).value(), Some(1.0));
+        assert_eq!(multi_arm.optimal_arm(), Some(1));
+        multi_arm.remove_arm(0);
+        multi_arm.remove_arm(0);
+        assert!(multi_arm.is_empty());
+        assert_eq!(multi_arm.optimal_arm(), None);
+    }
+
+    #[test]
+    fn run_bandit() {
+        let multi_arm = MultiArm::deterministic(&[1.0, 2.0]);
+        let mut bandit = StochasticBandit::greedy(2).with_biased_state(5.0);
+
+        // the optimistic bandit tries both arms once and then exploits the best arm
+        let episode = multi_arm.run(&mut bandit, 5, &mut StdRng::seed_from_u64(0));
+        assert_eq!(episode.arms.len(), 5);
+        assert!(episode.arms[2..].iter().all(|&arm| arm == 1));
+        assert_eq!(episode.rewards[2..], [2.0; 3]);
+        assert_eq!(episode.total_reward, 9.0);
+    }
+
+    #[test]
+    fn deterministic_arms() {
+        let multi_arm = MultiArm::deterministic(&[1.0, -2.5, 3.0]);
+
+        assert_eq!(multi_arm.optimal_arm(), Some(2));
+        assert_eq!(multi_arm.optimal_value(), Some(3.0));
+        assert_eq!(multi_arm.pull(0), 1.0);
+        assert_eq!(multi_arm.pull(1), -2.5);
+        assert_eq!(multi_arm.pull(2), 3.0);
     }
 }
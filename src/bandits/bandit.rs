@@ -1,4 +1,9 @@
-use rand::{random, Rng};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use rand_distr::Normal;
+use std::collections::VecDeque;
+use std::fmt;
 
 pub trait Bandit {
     /// Selects an arm to pull.
@@ -7,9 +12,36 @@ pub trait Bandit {
     fn receive_reward(&mut self, reward: f64);
     /// Restarts the bandit by clearing the internal state.
     fn restart(&mut self);
+    /// Returns a human-readable label describing the bandit, e.g., for benchmark results.
+    fn label(&self) -> String {
+        String::from("bandit")
+    }
+    /// Returns the arm the bandit currently recommends as the best one, or none if the bandit
+    /// makes no recommendation, e.g., in best-arm identification settings.
+    fn best_arm(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// A random number generator that can be cloned behind a trait object, so that bandits
+/// holding their own generator remain cloneable.
+trait CloneableRng: RngCore {
+    fn clone_box(&self) -> Box<dyn CloneableRng>;
+}
+
+impl<R: RngCore + Clone + 'static> CloneableRng for R {
+    fn clone_box(&self) -> Box<dyn CloneableRng> {
+        Box::new(self.clone())
+    }
 }
 
-#[derive(Debug, Default, Clone, PartialEq)]
+impl Clone for Box<dyn CloneableRng> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+#[derive(Clone)]
 struct BanditState {
     steps: usize,
     n_available_arms: usize,
@@ -17,6 +49,24 @@ struct BanditState {
     arm_pulls: Vec<usize>,
     initial_value: f64,
     estimated_arm_values: Vec<f64>,
+    reward_means: Vec<f64>,
+    reward_deviations: Vec<f64>,
+    rng: Box<dyn CloneableRng>,
+}
+
+impl fmt::Debug for BanditState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BanditState")
+            .field("steps", &self.steps)
+            .field("n_available_arms", &self.n_available_arms)
+            .field("selected_arm", &self.selected_arm)
+            .field("arm_pulls", &self.arm_pulls)
+            .field("initial_value", &self.initial_value)
+            .field("estimated_arm_values", &self.estimated_arm_values)
+            .field("reward_means", &self.reward_means)
+            .field("reward_deviations", &self.reward_deviations)
+            .finish_non_exhaustive()
+    }
 }
 
 impl BanditState {
@@ -31,6 +81,9 @@ impl BanditState {
             arm_pulls: vec![0; n_available_arms],
             initial_value: 0_f64,
             estimated_arm_values: vec![0_f64; n_available_arms],
+            reward_means: vec![0_f64; n_available_arms],
+            reward_deviations: vec![0_f64; n_available_arms],
+            rng: Box::new(rand::thread_rng()),
         }
     }
 
@@ -47,19 +100,77 @@ impl BanditState {
             arm_pulls: vec![0; n_available_arms],
             initial_value,
             estimated_arm_values: vec![initial_value; n_available_arms],
+            reward_means: vec![0_f64; n_available_arms],
+            reward_deviations: vec![0_f64; n_available_arms],
+            rng: Box::new(rand::thread_rng()),
         }
     }
+
+    /// Updates the running mean and the sum of squared deviations of the rewards received
+    /// by the selected arm, using Welford's algorithm. Note that the arm pulls should
+    /// already account for the given reward.
+    ///
+    /// - `reward` - the reward received by the selected arm.
+    fn track_reward(&mut self, reward: f64) {
+        let arm = self.selected_arm;
+        let delta = reward - self.reward_means[arm];
+        self.reward_means[arm] += delta / self.arm_pulls[arm] as f64;
+        self.reward_deviations[arm] += delta * (reward - self.reward_means[arm]);
+    }
 }
 
 #[derive(Debug, Clone)]
 enum BanditAlgorithm {
     EpsilonGreedy(EpsilonGreedy),
     Ucb(Ucb),
+    SlidingUcb(SlidingUcb),
+    Gradient(Gradient),
+    Thompson,
+    Softmax(Softmax),
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 struct EpsilonGreedy {
-    epsilon: f64,
+    epsilon: Schedule,
+}
+
+/// A schedule determines the value of a parameter, such as the exploration probability of
+/// an ε-greedy bandit, at each step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Schedule {
+    /// A fixed value.
+    Constant(f64),
+    /// A value decaying as `initial / (1 + decay * t)`.
+    InverseDecay { initial: f64, decay: f64 },
+    /// A value decaying as `initial * decay^t`, where the decay lies in [0, 1].
+    ExponentialDecay { initial: f64, decay: f64 },
+}
+
+impl Schedule {
+    /// Returns the value of the schedule at the given step.
+    ///
+    /// - `step` - the number of steps taken so far.
+    pub fn value(&self, step: usize) -> f64 {
+        match *self {
+            Schedule::Constant(value) => value,
+            Schedule::InverseDecay { initial, decay } => initial / (1.0 + decay * step as f64),
+            Schedule::ExponentialDecay { initial, decay } => initial * decay.powi(step as i32),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Schedule::Constant(value) => write!(f, "{value}"),
+            Schedule::InverseDecay {
+                initial,
+                decay: 1.0,
+            } => write!(f, "{initial}/(1+t)"),
+            Schedule::InverseDecay { initial, decay } => write!(f, "{initial}/(1+{decay}t)"),
+            Schedule::ExponentialDecay { initial, decay } => write!(f, "{initial}·{decay}^t"),
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -67,11 +178,192 @@ struct Ucb {
     exploration_degree: f64,
 }
 
+impl Ucb {
+    /// Returns the exploration bonus, that is, the confidence width of each arm estimate.
+    /// Arms that have not been pulled yet have an infinite bonus, so that each arm is pulled
+    /// once before the confidence bounds apply.
+    fn confidence_bonus(&self, state: &BanditState) -> Vec<f64> {
+        state
+            .arm_pulls
+            .iter()
+            .map(|&pulls| {
+                if pulls == 0 {
+                    f64::INFINITY
+                } else {
+                    self.exploration_degree * f64::sqrt(f64::ln(state.steps as f64) / pulls as f64)
+                }
+            })
+            .collect()
+    }
+}
+
+/// UCB over a sliding window, that is, the arm values and pulls are estimated only from the
+/// most recent rewards, so that the estimates follow non-stationary arms.
+#[derive(Debug, Default, Clone)]
+struct SlidingUcb {
+    exploration_degree: f64,
+    window: usize,
+    history: VecDeque<(usize, f64)>,
+}
+
+impl SlidingUcb {
+    /// Returns the number of pulls of each arm within the window.
+    fn window_pulls(&self, arms: usize) -> Vec<usize> {
+        let mut pulls = vec![0; arms];
+        for &(arm, _) in &self.history {
+            pulls[arm] += 1;
+        }
+        pulls
+    }
+
+    /// Returns the exploration bonus of each arm, computed as in UCB, but using the pulls
+    /// within the window and the length of the window instead of the total steps.
+    fn confidence_bonus(&self, state: &BanditState) -> Vec<f64> {
+        let steps = self.history.len() as f64;
+        self.window_pulls(state.n_available_arms)
+            .into_iter()
+            .map(|pulls| {
+                if pulls == 0 {
+                    f64::INFINITY
+                } else {
+                    self.exploration_degree * f64::sqrt(f64::ln(steps) / pulls as f64)
+                }
+            })
+            .collect()
+    }
+
+    /// Records the reward of the selected arm, forgets the rewards falling out of the window
+    /// and sets the estimated value of each arm to the mean of its rewards within the window.
+    /// Arms having no rewards within the window fall back to the initial value.
+    fn update(&mut self, state: &mut BanditState, reward: f64) {
+        self.history.push_back((state.selected_arm, reward));
+        if self.history.len() > self.window {
+            self.history.pop_front();
+        }
+
+        let mut sums = vec![0_f64; state.n_available_arms];
+        for &(arm, reward) in &self.history {
+            sums[arm] += reward;
+        }
+        for (arm, pulls) in self
+            .window_pulls(state.n_available_arms)
+            .into_iter()
+            .enumerate()
+        {
+            state.estimated_arm_values[arm] = if pulls == 0 {
+                state.initial_value
+            } else {
+                sums[arm] / pulls as f64
+            };
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct Softmax {
+    temperature: f64,
+}
+
+/// Returns the parameters (mean, standard deviation) of the Gaussian posterior over the
+/// value of each arm. The prior is a unit-variance normal centered at the initial value of
+/// the arms and counts as a single pseudo-observation. Until an arm has received at least two
+/// rewards, the reward variance is assumed to be one.
+fn gaussian_posterior(state: &BanditState) -> Vec<(f64, f64)> {
+    (0..state.n_available_arms)
+        .map(|arm| {
+            let pulls = state.arm_pulls[arm] as f64;
+            let variance = if state.arm_pulls[arm] > 1 {
+                state.reward_deviations[arm] / (pulls - 1.0)
+            } else {
+                1.0
+            };
+            let mean = (state.initial_value + pulls * state.reward_means[arm]) / (pulls + 1.0);
+
+            (mean, f64::sqrt(variance / (pulls + 1.0)))
+        })
+        .collect()
+}
+
+/// The baseline against which rewards are compared when updating the preferences of a
+/// gradient bandit. Rewards above the baseline increase the preference of the selected arm,
+/// while rewards below the baseline decrease it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Baseline {
+    /// Rewards are used as they are.
+    None,
+    /// The average of all rewards received so far, including the current one.
+    RunningMean,
+    /// A fixed reference reward.
+    Constant(f64),
+}
+
+impl From<bool> for Baseline {
+    /// Toggles the baseline, that is, the running mean when enabled and none otherwise.
+    fn from(enabled: bool) -> Self {
+        if enabled {
+            Baseline::RunningMean
+        } else {
+            Baseline::None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Gradient {
+    step_size: f64,
+    baseline: Baseline,
+    preferences: Vec<f64>,
+    average_reward: f64,
+}
+
+impl Gradient {
+    /// Updates the preferences of all arms given the reward received by the selected arm.
+    ///
+    /// - `state` - the internal state, already accounting for the given reward.
+    /// - `reward` - the reward received by the selected arm.
+    fn update(&mut self, state: &BanditState, reward: f64) {
+        self.average_reward += (reward - self.average_reward) / state.steps as f64;
+        let baseline = match self.baseline {
+            Baseline::None => 0.0,
+            Baseline::RunningMean => self.average_reward,
+            Baseline::Constant(value) => value,
+        };
+
+        let probabilities = softmax(&self.preferences);
+        for (arm, preference) in self.preferences.iter_mut().enumerate() {
+            let indicator = if arm == state.selected_arm { 1.0 } else { 0.0 };
+            *preference += self.step_size * (reward - baseline) * (indicator - probabilities[arm]);
+        }
+    }
+}
+
+/// Returns the index of the maximum value, breaking ties uniformly at random.
+fn random_argmax<R: Rng + ?Sized>(values: &[f64], rng: &mut R) -> usize {
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let indices: Vec<usize> = (0..values.len()).filter(|&i| values[i] == max).collect();
+
+    *indices.choose(rng).unwrap()
+}
+
+/// Returns the softmax distribution of the given values. The maximum value is subtracted
+/// before exponentiating to avoid overflow.
+fn softmax(values: &[f64]) -> Vec<f64> {
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let weights: Vec<f64> = values.iter().map(|v| (v - max).exp()).collect();
+    let total: f64 = weights.iter().sum();
+
+    weights.iter().map(|w| w / total).collect()
+}
+
 /// Stochastic bandits support the following algorithms:
 ///
 /// - greedy
 /// - ε-greedy
 /// - Upper Confidence Bound (UCB)
+/// - sliding-window UCB
+/// - gradient bandit
+/// - Thompson sampling
+/// - softmax (Boltzmann exploration)
 #[derive(Debug, Clone)]
 pub struct StochasticBandit {
     state: BanditState,
@@ -88,7 +380,9 @@ impl StochasticBandit {
     pub fn greedy(arms: usize) -> StochasticBandit {
         StochasticBandit {
             state: BanditState::new(arms),
-            algorithm: BanditAlgorithm::EpsilonGreedy(EpsilonGreedy { epsilon: 0_f64 }),
+            algorithm: BanditAlgorithm::EpsilonGreedy(EpsilonGreedy {
+                epsilon: Schedule::Constant(0_f64),
+            }),
             learning_rate: None,
         }
     }
@@ -101,6 +395,32 @@ impl StochasticBandit {
     /// - `arms` - the number of available arms.
     /// - `epsilon` - exploration probability.
     pub fn epsilon_greedy(arms: usize, epsilon: f64) -> StochasticBandit {
+        StochasticBandit {
+            state: BanditState::new(arms),
+            algorithm: BanditAlgorithm::EpsilonGreedy(EpsilonGreedy {
+                epsilon: Schedule::Constant(epsilon),
+            }),
+            learning_rate: None,
+        }
+    }
+
+    /// Creates an epsilon-greedy stochastic bandit whose exploration probability follows
+    /// a schedule over the steps, e.g., a decaying one. Decaying schedules explore a lot
+    /// early on and behave asymptotically as the greedy bandit.
+    ///
+    /// - `arms` - the number of available arms.
+    /// - `epsilon` - exploration probability schedule.
+    pub fn epsilon_greedy_with_schedule(arms: usize, epsilon: Schedule) -> StochasticBandit {
+        match epsilon {
+            Schedule::InverseDecay { decay, .. } if decay < 0.0 => {
+                panic!("Invalid decay value: {decay}")
+            }
+            Schedule::ExponentialDecay { decay, .. } if !(0.0..=1.0).contains(&decay) => {
+                panic!("Invalid decay value: {decay}")
+            }
+            _ => {}
+        }
+
         StochasticBandit {
             state: BanditState::new(arms),
             algorithm: BanditAlgorithm::EpsilonGreedy(EpsilonGreedy { epsilon }),
@@ -108,6 +428,22 @@ impl StochasticBandit {
         }
     }
 
+    /// Creates an epsilon-greedy stochastic bandit whose exploration probability decays as
+    /// `epsilon0 / (1 + decay * t)`, where `t` is the number of steps since the last restart.
+    ///
+    /// - `arms` - the number of available arms.
+    /// - `epsilon0` - initial exploration probability.
+    /// - `decay` - non-negative decay rate.
+    pub fn epsilon_greedy_decaying(arms: usize, epsilon0: f64, decay: f64) -> StochasticBandit {
+        StochasticBandit::epsilon_greedy_with_schedule(
+            arms,
+            Schedule::InverseDecay {
+                initial: epsilon0,
+                decay,
+            },
+        )
+    }
+
     /// Creates an Upper-Confidence-Bound (UCB) stochastic bandit. In contrast to the
     /// epsilon-greedy bandit, which explores actions with no preference for those that
     /// are nearly greedy or particularly uncertain, UCB takes into account both how close
@@ -123,6 +459,113 @@ impl StochasticBandit {
         }
     }
 
+    /// Creates a sliding-window UCB stochastic bandit for non-stationary arms. In contrast to
+    /// the UCB bandit, the estimated values and the confidence bonuses take into account only
+    /// the rewards of the last `window` steps, so that the bandit forgets outdated rewards
+    /// and tracks a changing optimal arm. Note that the learning rate is ignored, since the
+    /// estimated values are the means of the rewards within the window.
+    ///
+    /// - `arms` - the number of available arms.
+    /// - `exploration_degree` - the degree of exploration
+    /// - `window` - the number of most recent steps taken into account.
+    pub fn sliding_ucb(arms: usize, exploration_degree: f64, window: usize) -> StochasticBandit {
+        if window == 0 {
+            panic!("Invalid window value: {window}");
+        }
+
+        StochasticBandit {
+            state: BanditState::new(arms),
+            algorithm: BanditAlgorithm::SlidingUcb(SlidingUcb {
+                exploration_degree,
+                window,
+                history: VecDeque::with_capacity(window + 1),
+            }),
+            learning_rate: None,
+        }
+    }
+
+    /// Creates a gradient stochastic bandit. In contrast to the value-based bandits, the
+    /// gradient bandit learns a numerical preference for each arm and selects arms according
+    /// to a softmax distribution over the preferences. After each reward, the preferences are
+    /// updated by stochastic gradient ascent, relative to a baseline, which by default is the
+    /// average of all rewards received so far.
+    ///
+    /// - `arms` - the number of available arms.
+    /// - `step_size` - the step size of the preference updates.
+    pub fn gradient(arms: usize, step_size: f64) -> StochasticBandit {
+        if step_size <= 0.0 {
+            panic!("Invalid step size value: {step_size}");
+        }
+
+        StochasticBandit {
+            state: BanditState::new(arms),
+            algorithm: BanditAlgorithm::Gradient(Gradient {
+                step_size,
+                baseline: Baseline::RunningMean,
+                preferences: vec![0_f64; arms],
+                average_reward: 0_f64,
+            }),
+            learning_rate: None,
+        }
+    }
+
+    /// Creates a Thompson sampling stochastic bandit. Thompson sampling maintains a Gaussian
+    /// posterior over the value of each arm, draws a sample from every posterior and selects
+    /// the arm having the highest sample. Arms are thus selected according to the probability
+    /// of being optimal, and exploration fades out as the posteriors concentrate.
+    ///
+    /// The prior of each arm is a unit-variance normal centered at zero, or at the initial
+    /// value given by `with_biased_state`, weighted as a single observation.
+    ///
+    /// - `arms` - the number of available arms.
+    pub fn thompson_sampling(arms: usize) -> StochasticBandit {
+        StochasticBandit {
+            state: BanditState::new(arms),
+            algorithm: BanditAlgorithm::Thompson,
+            learning_rate: None,
+        }
+    }
+
+    /// Creates a softmax stochastic bandit, also known as Boltzmann exploration. In contrast
+    /// to the epsilon-greedy bandit, which explores all actions uniformly, the softmax bandit
+    /// selects each arm with probability proportional to `exp(Q(a) / τ)`, thus exploring
+    /// arms having higher estimated values more often. High temperatures make the selection
+    /// nearly uniform, while low temperatures approximate the greedy bandit.
+    ///
+    /// - `arms` - the number of available arms.
+    /// - `temperature` - the temperature τ controlling the degree of exploration.
+    pub fn softmax(arms: usize, temperature: f64) -> StochasticBandit {
+        if temperature <= 0.0 {
+            panic!("Invalid temperature value: {temperature}");
+        }
+
+        StochasticBandit {
+            state: BanditState::new(arms),
+            algorithm: BanditAlgorithm::Softmax(Softmax { temperature }),
+            learning_rate: None,
+        }
+    }
+
+    /// Sets the baseline of a gradient bandit. The baseline does not affect other algorithms.
+    /// A boolean may be given instead, in order to toggle the running mean baseline on or off.
+    ///
+    /// - `baseline` - the baseline against which rewards are compared.
+    pub fn with_baseline(self, baseline: impl Into<Baseline>) -> StochasticBandit {
+        let baseline = baseline.into();
+        let algorithm = match self.algorithm {
+            BanditAlgorithm::Gradient(bandit) => {
+                BanditAlgorithm::Gradient(Gradient { baseline, ..bandit })
+            }
+            algorithm => algorithm,
+        };
+
+        StochasticBandit {
+            state: self.state,
+            algorithm,
+            learning_rate: self.learning_rate,
+        }
+    }
+
     pub fn with_constant_learning_rate(self, learning_rate: f64) -> StochasticBandit {
         if learning_rate <= 0.0 || learning_rate > 1.0 {
             panic!("Invalid alpha value: {learning_rate}");
@@ -137,42 +580,95 @@ impl StochasticBandit {
 
     pub fn with_biased_state(self, value: f64) -> StochasticBandit {
         StochasticBandit {
-            state: BanditState::biased(self.state.n_available_arms, value),
+            state: BanditState {
+                rng: self.state.rng,
+                ..BanditState::biased(self.state.n_available_arms, value)
+            },
+            algorithm: self.algorithm,
+            learning_rate: self.learning_rate,
+        }
+    }
+
+    /// Sets the random number generator used for selecting arms, which by default is the
+    /// thread-local generator. A seeded generator makes the arm selections reproducible.
+    /// Note that restarting the bandit does not reset the generator.
+    ///
+    /// - `rng` - the random number generator.
+    pub fn with_rng<R: Rng + Clone + 'static>(self, rng: R) -> StochasticBandit {
+        StochasticBandit {
+            state: BanditState {
+                rng: Box::new(rng),
+                ..self.state
+            },
             algorithm: self.algorithm,
             learning_rate: self.learning_rate,
         }
     }
+
+    /// Returns the current estimated value of each arm.
+    pub fn estimated_values(&self) -> &[f64] {
+        &self.state.estimated_arm_values
+    }
+
+    /// Returns the number of times each arm has been pulled since the last restart.
+    pub fn arm_pulls(&self) -> &[usize] {
+        &self.state.arm_pulls
+    }
+
+    /// Returns the number of rewards received since the last restart.
+    pub fn steps(&self) -> usize {
+        self.state.steps
+    }
+
+    /// Returns the running estimate of the reward distribution of an arm as a pair of
+    /// (mean, variance), computed from all rewards the arm received since the last restart.
+    /// In contrast to the estimated arm value, the estimate is always a sample average,
+    /// regardless of the learning rate. The variance is the sample variance, which is zero
+    /// until the arm has received at least two rewards.
+    ///
+    /// - `arm` - the arm of interest.
+    pub fn estimated_distribution(&self, arm: usize) -> (f64, f64) {
+        let pulls = self.state.arm_pulls[arm];
+        let variance = if pulls > 1 {
+            self.state.reward_deviations[arm] / (pulls - 1) as f64
+        } else {
+            0.0
+        };
+
+        (self.state.reward_means[arm], variance)
+    }
+
+    /// Returns the current exploration bonus `c * sqrt(ln t / n_i)` of each arm for a UCB
+    /// bandit, or none for any other algorithm. The bonus is the confidence width around
+    /// each estimated arm value and shrinks as an arm is pulled more often. Note that the
+    /// bonus is infinite for arms that have not been pulled yet, or, for a sliding-window UCB
+    /// bandit, not pulled within the window.
+    pub fn confidence_bonus(&self) -> Option<Vec<f64>> {
+        match &self.algorithm {
+            BanditAlgorithm::Ucb(bandit) => Some(bandit.confidence_bonus(&self.state)),
+            BanditAlgorithm::SlidingUcb(bandit) => Some(bandit.confidence_bonus(&self.state)),
+            _ => None,
+        }
+    }
 }
 
 impl Bandit for StochasticBandit {
     fn select_arm(&mut self) -> usize {
         if self.state.steps == 0 {
-            self.state.selected_arm = self
-                .state
-                .estimated_arm_values
-                .iter()
-                .enumerate()
-                .max_by(|(_, a), (_, b)| a.total_cmp(b))
-                .map(|(index, _)| index)
-                .unwrap();
+            self.state.selected_arm =
+                random_argmax(&self.state.estimated_arm_values, &mut self.state.rng);
         }
 
         match &self.algorithm {
             BanditAlgorithm::EpsilonGreedy(bandit) => {
                 // select the next action either randomly or according to the maximum estimated value
-                let exploration_probability: f64 = random();
-                if exploration_probability > 1.0 - bandit.epsilon {
+                let exploration_probability: f64 = self.state.rng.gen();
+                if exploration_probability > 1.0 - bandit.epsilon.value(self.state.steps) {
                     self.state.selected_arm =
-                        rand::thread_rng().gen_range(0..self.state.n_available_arms);
+                        self.state.rng.gen_range(0..self.state.n_available_arms);
                 } else {
-                    self.state.selected_arm = self
-                        .state
-                        .estimated_arm_values
-                        .iter()
-                        .enumerate()
-                        .max_by(|(_, a), (_, b)| a.total_cmp(b))
-                        .map(|(index, _)| index)
-                        .unwrap();
+                    self.state.selected_arm =
+                        random_argmax(&self.state.estimated_arm_values, &mut self.state.rng);
                 }
             }
             BanditAlgorithm::Ucb(bandit) => {
@@ -180,18 +676,53 @@ impl Bandit for StochasticBandit {
                     .state
                     .estimated_arm_values
                     .iter()
+                    .zip(bandit.confidence_bonus(&self.state))
+                    .map(|(v, bonus)| v + bonus)
                     .enumerate()
-                    .map(|(i, v)| {
-                        v + bandit.exploration_degree
-                            * f64::sqrt(
-                                f64::ln(self.state.steps as f64) / self.state.arm_pulls[i] as f64,
-                            )
-                    })
+                    .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .map(|(index, _)| index)
+                    .unwrap();
+            }
+            BanditAlgorithm::SlidingUcb(bandit) => {
+                self.state.selected_arm = self
+                    .state
+                    .estimated_arm_values
+                    .iter()
+                    .zip(bandit.confidence_bonus(&self.state))
+                    .map(|(v, bonus)| v + bonus)
                     .enumerate()
                     .max_by(|(_, a), (_, b)| a.total_cmp(b))
                     .map(|(index, _)| index)
                     .unwrap();
             }
+            BanditAlgorithm::Gradient(bandit) => {
+                // sample the next action according to the softmax distribution of preferences
+                self.state.selected_arm = WeightedIndex::new(softmax(&bandit.preferences))
+                    .unwrap()
+                    .sample(&mut self.state.rng);
+            }
+            BanditAlgorithm::Thompson => {
+                // sample a value for each arm from its posterior and select the highest
+                self.state.selected_arm = gaussian_posterior(&self.state)
+                    .into_iter()
+                    .map(|(mean, std)| Normal::new(mean, std).unwrap().sample(&mut self.state.rng))
+                    .enumerate()
+                    .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .map(|(index, _)| index)
+                    .unwrap();
+            }
+            BanditAlgorithm::Softmax(bandit) => {
+                // sample the next action according to the Boltzmann distribution of the values
+                let scaled_values: Vec<f64> = self
+                    .state
+                    .estimated_arm_values
+                    .iter()
+                    .map(|value| value / bandit.temperature)
+                    .collect();
+                self.state.selected_arm = WeightedIndex::new(softmax(&scaled_values))
+                    .unwrap()
+                    .sample(&mut self.state.rng);
+            }
         }
 
         self.state.selected_arm
@@ -209,7 +740,17 @@ impl Bandit for StochasticBandit {
 
         // update the estimated value for the best action
         self.state.estimated_arm_values[self.state.selected_arm] +=
-            alpha * (reward - self.state.estimated_arm_values[self.state.selected_arm])
+            alpha * (reward - self.state.estimated_arm_values[self.state.selected_arm]);
+
+        // update the estimated reward distribution
+        self.state.track_reward(reward);
+
+        // update the arm preferences, or the estimates within the window
+        match &mut self.algorithm {
+            BanditAlgorithm::Gradient(bandit) => bandit.update(&self.state, reward),
+            BanditAlgorithm::SlidingUcb(bandit) => bandit.update(&mut self.state, reward),
+            _ => {}
+        }
     }
 
     fn restart(&mut self) {
@@ -218,12 +759,315 @@ impl Bandit for StochasticBandit {
         self.state.arm_pulls = vec![0; self.state.n_available_arms];
         self.state.estimated_arm_values =
             vec![self.state.initial_value; self.state.n_available_arms];
+        self.state.reward_means = vec![0_f64; self.state.n_available_arms];
+        self.state.reward_deviations = vec![0_f64; self.state.n_available_arms];
+
+        match &mut self.algorithm {
+            BanditAlgorithm::Gradient(bandit) => {
+                bandit.preferences = vec![0_f64; self.state.n_available_arms];
+                bandit.average_reward = 0_f64;
+            }
+            BanditAlgorithm::SlidingUcb(bandit) => bandit.history.clear(),
+            _ => {}
+        }
+    }
+
+    fn label(&self) -> String {
+        match &self.algorithm {
+            BanditAlgorithm::EpsilonGreedy(bandit) if bandit.epsilon == Schedule::Constant(0.0) => {
+                String::from("greedy")
+            }
+            BanditAlgorithm::EpsilonGreedy(bandit) => format!("ε-greedy (ε={})", bandit.epsilon),
+            BanditAlgorithm::Ucb(bandit) => format!("UCB (c={})", bandit.exploration_degree),
+            BanditAlgorithm::SlidingUcb(bandit) => format!(
+                "sliding UCB (c={}, window={})",
+                bandit.exploration_degree, bandit.window
+            ),
+            BanditAlgorithm::Gradient(bandit) => match bandit.baseline {
+                Baseline::None => format!("gradient (α={}, no baseline)", bandit.step_size),
+                Baseline::RunningMean => format!("gradient (α={})", bandit.step_size),
+                Baseline::Constant(value) => {
+                    format!("gradient (α={}, baseline={value})", bandit.step_size)
+                }
+            },
+            BanditAlgorithm::Thompson => String::from("Thompson sampling"),
+            BanditAlgorithm::Softmax(bandit) => format!("softmax (τ={})", bandit.temperature),
+        }
+    }
+
+    /// Recommends the arm having the highest estimated value, regardless of the algorithm.
+    fn best_arm(&self) -> Option<usize> {
+        self.state
+            .estimated_arm_values
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+}
+
+/// A meta bandit is a bandit over bandits. It holds several sub-bandits, each one being a
+/// different exploration strategy, and uses its own bandit layer to decide which sub-bandit
+/// arm choice to follow at each step. The observed reward is attributed back to the chosen
+/// sub-bandit and to the meta layer, which thus learns online which strategy works best.
+///
+/// Note that all sub-bandits should operate on the same set of arms.
+pub struct MetaBandit {
+    bandits: Vec<Box<dyn Bandit>>,
+    meta: Box<dyn Bandit>,
+    selected_bandit: usize,
+}
+
+impl MetaBandit {
+    /// Creates a meta bandit that selects among its sub-bandits using an epsilon-greedy layer.
+    ///
+    /// - `bandits` - the sub-bandits.
+    /// - `epsilon` - probability of following a random sub-bandit.
+    pub fn epsilon_greedy(bandits: Vec<Box<dyn Bandit>>, epsilon: f64) -> MetaBandit {
+        if bandits.is_empty() {
+            panic!("Meta bandit requires at least one sub-bandit");
+        }
+
+        MetaBandit {
+            meta: Box::new(StochasticBandit::epsilon_greedy(bandits.len(), epsilon)),
+            bandits,
+            selected_bandit: 0,
+        }
+    }
+}
+
+impl Bandit for MetaBandit {
+    fn select_arm(&mut self) -> usize {
+        self.selected_bandit = self.meta.select_arm();
+        self.bandits[self.selected_bandit].select_arm()
+    }
+
+    fn receive_reward(&mut self, reward: f64) {
+        self.bandits[self.selected_bandit].receive_reward(reward);
+        self.meta.receive_reward(reward);
+    }
+
+    fn restart(&mut self) {
+        self.selected_bandit = 0;
+        self.meta.restart();
+        self.bandits.iter_mut().for_each(|bandit| bandit.restart());
+    }
+
+    fn label(&self) -> String {
+        let labels: Vec<String> = self.bandits.iter().map(|bandit| bandit.label()).collect();
+        format!("meta [{}]", labels.join(", "))
+    }
+
+    /// Recommends the best arm of the sub-bandit that the meta layer considers the best.
+    fn best_arm(&self) -> Option<usize> {
+        self.meta
+            .best_arm()
+            .and_then(|bandit| self.bandits[bandit].best_arm())
+    }
+}
+
+/// Represents an online learner in the full-information setting, where the rewards of all
+/// arms, not only of the selected one, are revealed after each round.
+pub trait FullInformation {
+    /// Selects an arm to pull.
+    fn select_arm(&mut self) -> usize;
+    /// Updates the learner using the rewards of all arms for the last round.
+    fn update(&mut self, all_rewards: &[f64]);
+    /// Restarts the learner by clearing the internal state.
+    fn restart(&mut self);
+}
+
+/// Hedge, also known as multiplicative weights, maintains a weight per arm and selects arms
+/// with probability proportional to their weights. After each round, the weight of every
+/// arm is multiplied by `exp(eta * reward)`. In contrast to EXP3, no importance weighting is
+/// needed, since the rewards of all arms are observed.
+#[derive(Clone)]
+pub struct Hedge {
+    eta: f64,
+    log_weights: Vec<f64>,
+    rng: Box<dyn CloneableRng>,
+}
+
+impl fmt::Debug for Hedge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hedge")
+            .field("eta", &self.eta)
+            .field("log_weights", &self.log_weights)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Hedge {
+    /// Creates a Hedge learner having uniform initial weights.
+    ///
+    /// - `arms` - the number of available arms.
+    /// - `eta` - the learning rate.
+    pub fn new(arms: usize, eta: f64) -> Hedge {
+        if eta <= 0.0 {
+            panic!("Invalid eta value: {eta}");
+        }
+
+        Hedge {
+            eta,
+            log_weights: vec![0_f64; arms],
+            rng: Box::new(rand::thread_rng()),
+        }
+    }
+
+    /// Sets the random number generator used for selecting arms, which by default is the
+    /// thread-local generator. A seeded generator makes the arm selections reproducible.
+    /// Note that restarting the learner does not reset the generator.
+    ///
+    /// - `rng` - the random number generator.
+    pub fn with_rng<R: Rng + Clone + 'static>(self, rng: R) -> Hedge {
+        Hedge {
+            rng: Box::new(rng),
+            ..self
+        }
+    }
+
+    /// Returns the probability of selecting each arm, that is, the normalized weights.
+    pub fn probabilities(&self) -> Vec<f64> {
+        softmax(&self.log_weights)
+    }
+}
+
+impl FullInformation for Hedge {
+    fn select_arm(&mut self) -> usize {
+        WeightedIndex::new(self.probabilities())
+            .unwrap()
+            .sample(&mut self.rng)
+    }
+
+    fn update(&mut self, all_rewards: &[f64]) {
+        self.log_weights
+            .iter_mut()
+            .zip(all_rewards)
+            .for_each(|(w, reward)| *w += self.eta * reward);
+    }
+
+    fn restart(&mut self) {
+        self.log_weights = vec![0_f64; self.log_weights.len()];
+    }
+}
+
+/// EXP3, that is, exponential weights for exploration and exploitation, is a bandit for the
+/// adversarial setting, where the rewards may be chosen by an adversary instead of being
+/// sampled from fixed distributions. It maintains a weight per arm and selects arms according
+/// to the normalized weights, mixed with uniform exploration `γ`. Since only the reward of the
+/// selected arm is revealed, the weight of that arm is multiplied by `exp(γ r / (p K))`, where
+/// `r / p` is the importance-weighted reward. Rewards are assumed to lie in `[0, 1]`.
+#[derive(Clone)]
+pub struct Exp3 {
+    gamma: f64,
+    log_weights: Vec<f64>,
+    selected_arm: usize,
+    rng: Box<dyn CloneableRng>,
+}
+
+impl fmt::Debug for Exp3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Exp3")
+            .field("gamma", &self.gamma)
+            .field("log_weights", &self.log_weights)
+            .field("selected_arm", &self.selected_arm)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Exp3 {
+    /// Creates an EXP3 bandit having uniform initial weights.
+    ///
+    /// - `arms` - the number of available arms.
+    /// - `gamma` - the exploration probability, in (0, 1].
+    pub fn new(arms: usize, gamma: f64) -> Exp3 {
+        if gamma <= 0.0 || gamma > 1.0 {
+            panic!("Invalid gamma value: {gamma}");
+        }
+
+        Exp3 {
+            gamma,
+            log_weights: vec![0_f64; arms],
+            selected_arm: 0,
+            rng: Box::new(rand::thread_rng()),
+        }
+    }
+
+    /// Sets the random number generator used for selecting arms, which by default is the
+    /// thread-local generator. A seeded generator makes the arm selections reproducible.
+    /// Note that restarting the bandit does not reset the generator.
+    ///
+    /// - `rng` - the random number generator.
+    pub fn with_rng<R: Rng + Clone + 'static>(self, rng: R) -> Exp3 {
+        Exp3 {
+            rng: Box::new(rng),
+            ..self
+        }
+    }
+
+    /// Returns the probability of selecting each arm, that is, the normalized weights mixed
+    /// with the uniform distribution.
+    pub fn probabilities(&self) -> Vec<f64> {
+        let arms = self.log_weights.len() as f64;
+        softmax(&self.log_weights)
+            .into_iter()
+            .map(|p| (1.0 - self.gamma) * p + self.gamma / arms)
+            .collect()
+    }
+}
+
+impl Bandit for Exp3 {
+    fn select_arm(&mut self) -> usize {
+        self.selected_arm = WeightedIndex::new(self.probabilities())
+            .unwrap()
+            .sample(&mut self.rng);
+        self.selected_arm
+    }
+
+    fn receive_reward(&mut self, reward: f64) {
+        let arms = self.log_weights.len() as f64;
+        let estimated_reward = reward / self.probabilities()[self.selected_arm];
+        self.log_weights[self.selected_arm] += self.gamma * estimated_reward / arms;
+    }
+
+    fn restart(&mut self) {
+        self.log_weights = vec![0_f64; self.log_weights.len()];
+        self.selected_arm = 0;
+    }
+
+    fn label(&self) -> String {
+        format!("EXP3 (γ={})", self.gamma)
+    }
+
+    /// Recommends the arm having the highest weight.
+    fn best_arm(&self) -> Option<usize> {
+        self.log_weights
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bandits::arm::{MultiArm, ScriptEnd, ScriptedArm};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// A bandit that always selects the same arm.
+    struct Stubborn(usize);
+
+    impl Bandit for Stubborn {
+        fn select_arm(&mut self) -> usize {
+            self.0
+        }
+
+        fn receive_reward(&mut self, _: f64) {}
+
+        fn restart(&mut self) {}
+    }
 
     #[test]
     fn greedy_bandit() {
@@ -248,6 +1092,22 @@ mod tests {
         assert_ne!(greedy_bandit.select_arm(), 0);
     }
 
+    #[test]
+    fn random_tie_breaking() {
+        let mut bandit = StochasticBandit::greedy(4);
+
+        // all estimates are equal, so every arm should be selected about a quarter of the time
+        let mut selections = [0; 4];
+        for _ in 0..4000 {
+            selections[bandit.select_arm()] += 1;
+        }
+        assert!(selections.iter().all(|&n| n > 800 && n < 1200));
+
+        // ties are broken only among the arms sharing the maximum value
+        bandit.state.estimated_arm_values = vec![1.0, 0.0, 1.0, 0.0];
+        assert!((0..100).all(|_| [0, 2].contains(&bandit.select_arm())));
+    }
+
     #[test]
     fn epsilon_greedy_bandit() {
         let epsilon_greedy_bandit = StochasticBandit::epsilon_greedy(10, 0.05);
@@ -275,9 +1135,407 @@ mod tests {
         assert_eq!(bandit.state.estimated_arm_values, vec![1.5; 5]);
     }
 
+    #[test]
+    fn accessors() {
+        let mut bandit = StochasticBandit::greedy(3).with_biased_state(0.5);
+        assert_eq!(bandit.estimated_values(), &[0.5; 3]);
+        assert_eq!(bandit.steps(), 0);
+
+        for (arm, reward) in [(1, 2.0), (1, 4.0), (2, 1.0)] {
+            bandit.state.selected_arm = arm;
+            bandit.receive_reward(reward);
+        }
+
+        assert_eq!(bandit.estimated_values(), &[0.5, 3.0, 1.0]);
+        assert_eq!(bandit.arm_pulls(), &[0, 2, 1]);
+        assert_eq!(bandit.steps(), 3);
+    }
+
+    #[test]
+    fn best_arm() {
+        let mut bandit = StochasticBandit::ucb(3, 2.0);
+        for (arm, reward) in [(0, 1.0), (1, 3.0), (2, 2.0)] {
+            bandit.state.selected_arm = arm;
+            bandit.receive_reward(reward);
+        }
+        assert_eq!(bandit.best_arm(), Some(1));
+        assert_eq!(Stubborn(0).best_arm(), None);
+    }
+
+    #[test]
+    fn epsilon_schedules() {
+        let schedule = Schedule::InverseDecay {
+            initial: 1.0,
+            decay: 1.0,
+        };
+        assert_eq!(schedule.value(0), 1.0);
+        assert_eq!(schedule.value(3), 0.25);
+
+        let schedule = Schedule::ExponentialDecay {
+            initial: 0.5,
+            decay: 0.5,
+        };
+        assert_eq!(schedule.value(2), 0.125);
+
+        // asymptotically, the decaying bandit behaves as the greedy bandit
+        let mut bandit = StochasticBandit::epsilon_greedy_with_schedule(4, schedule);
+        bandit.state.estimated_arm_values = vec![0.0, 0.0, 1.0, 0.0];
+        bandit.state.steps = 1000;
+        assert!((0..1000).all(|_| bandit.select_arm() == 2));
+    }
+
+    #[test]
+    fn decaying_epsilon_greedy() {
+        let mut bandit = StochasticBandit::epsilon_greedy_decaying(4, 1.0, 1.0);
+        let explorations = |bandit: &mut StochasticBandit| {
+            bandit.state.estimated_arm_values = vec![0.0, 0.0, 1.0, 0.0];
+            (0..1000).filter(|_| bandit.select_arm() != 2).count()
+        };
+
+        // initially every selection is random, while after many steps almost none is
+        assert!(explorations(&mut bandit) > 500);
+        bandit.state.steps = 1_000_000;
+        assert!(explorations(&mut bandit) < 10);
+
+        // restarting resets the schedule
+        bandit.restart();
+        assert!(explorations(&mut bandit) > 500);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid decay value: -1")]
+    fn negative_epsilon_decay() {
+        StochasticBandit::epsilon_greedy_decaying(4, 1.0, -1.0);
+    }
+
+    #[test]
+    fn sliding_ucb_bandit() {
+        // the optimal arm switches from the first to the second one half way
+        let run = |bandit: &mut StochasticBandit| {
+            (0..1000)
+                .map(|step| {
+                    let arm = bandit.select_arm();
+                    bandit.receive_reward(if (step < 500) == (arm == 0) { 1.0 } else { 0.0 });
+                    arm
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut bandit = StochasticBandit::sliding_ucb(2, 0.5, 50);
+        let sliding_arms = run(&mut bandit);
+        let ucb_arms = run(&mut StochasticBandit::ucb(2, 0.5));
+
+        // the window forgets the rewards before the switch, so the new optimal arm is tracked
+        let switches = |arms: &[usize]| arms[500..600].iter().filter(|&&arm| arm == 1).count();
+        assert!(switches(&sliding_arms) > switches(&ucb_arms));
+        assert!(sliding_arms[900..].iter().filter(|&&arm| arm == 1).count() > 80);
+        assert_eq!(bandit.best_arm(), Some(1));
+        assert_eq!(bandit.label(), "sliding UCB (c=0.5, window=50)");
+
+        bandit.restart();
+        assert_eq!(bandit.confidence_bonus(), Some(vec![f64::INFINITY; 2]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid window value: 0")]
+    fn zero_window_sliding_ucb() {
+        StochasticBandit::sliding_ucb(2, 0.5, 0);
+    }
+
+    #[test]
+    fn estimated_distribution() {
+        let mut bandit = StochasticBandit::greedy(2).with_constant_learning_rate(0.5);
+        assert_eq!(bandit.estimated_distribution(0), (0.0, 0.0));
+
+        for reward in [2.0, 4.0, 9.0] {
+            bandit.state.selected_arm = 0;
+            bandit.receive_reward(reward);
+        }
+
+        // the distribution estimate is a sample average regardless of the learning rate
+        assert_eq!(bandit.estimated_distribution(0), (5.0, 13.0));
+        assert_eq!(bandit.estimated_distribution(1), (0.0, 0.0));
+
+        bandit.restart();
+        assert_eq!(bandit.estimated_distribution(0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn ucb_confidence_bonus() {
+        assert!(StochasticBandit::greedy(2).confidence_bonus().is_none());
+
+        let mut bandit = StochasticBandit::ucb(2, 2.0);
+        for arm in [0, 1, 1, 1] {
+            bandit.state.selected_arm = arm;
+            bandit.receive_reward(1.0);
+        }
+
+        let bonus = bandit.confidence_bonus().unwrap();
+        assert!((bonus[0] - 2.0 * f64::sqrt(f64::ln(4.0))).abs() < 1e-12);
+        assert!((bonus[1] - 2.0 * f64::sqrt(f64::ln(4.0) / 3.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn ucb_pulls_each_arm_first() {
+        let mut bandit = StochasticBandit::ucb(5, 2.0).with_biased_state(1.0);
+
+        let mut arms: Vec<usize> = (0..3)
+            .map(|_| {
+                let arm = bandit.select_arm();
+                bandit.receive_reward(10.0);
+                arm
+            })
+            .collect();
+        arms.sort();
+        arms.dedup();
+        assert_eq!(arms.len(), 3);
+
+        let bonus = bandit.confidence_bonus().unwrap();
+        assert_eq!(bonus.iter().filter(|b| b.is_infinite()).count(), 2);
+    }
+
     #[test]
     #[should_panic(expected = "Invalid alpha value: 0")]
     fn zero_learning_rate() {
         StochasticBandit::greedy(5).with_constant_learning_rate(0.0);
     }
+
+    #[test]
+    fn meta_bandit() {
+        let mut bandit =
+            MetaBandit::epsilon_greedy(vec![Box::new(Stubborn(0)), Box::new(Stubborn(1))], 0.1);
+
+        // arm 1 is always better, so the meta layer should mostly follow the second bandit
+        let mut optimal_selections = 0;
+        for _ in 0..1000 {
+            let arm = bandit.select_arm();
+            optimal_selections += arm;
+            bandit.receive_reward(arm as f64);
+        }
+        assert!(optimal_selections > 900);
+
+        bandit.restart();
+        assert_eq!(bandit.selected_bandit, 0);
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(StochasticBandit::greedy(2).label(), "greedy");
+        assert_eq!(
+            StochasticBandit::epsilon_greedy(2, 0.1).label(),
+            "ε-greedy (ε=0.1)"
+        );
+        assert_eq!(StochasticBandit::ucb(2, 2.0).label(), "UCB (c=2)");
+        assert_eq!(
+            StochasticBandit::epsilon_greedy_with_schedule(
+                2,
+                Schedule::InverseDecay {
+                    initial: 1.0,
+                    decay: 1.0
+                }
+            )
+            .label(),
+            "ε-greedy (ε=1/(1+t))"
+        );
+        assert_eq!(
+            StochasticBandit::epsilon_greedy_with_schedule(
+                2,
+                Schedule::ExponentialDecay {
+                    initial: 0.5,
+                    decay: 0.99
+                }
+            )
+            .label(),
+            "ε-greedy (ε=0.5·0.99^t)"
+        );
+        assert_eq!(
+            StochasticBandit::gradient(2, 0.1)
+                .with_baseline(Baseline::None)
+                .label(),
+            "gradient (α=0.1, no baseline)"
+        );
+        assert_eq!(
+            StochasticBandit::gradient(2, 0.1)
+                .with_baseline(false)
+                .with_baseline(true)
+                .label(),
+            "gradient (α=0.1)"
+        );
+        assert_eq!(
+            StochasticBandit::thompson_sampling(2).label(),
+            "Thompson sampling"
+        );
+        assert_eq!(
+            MetaBandit::epsilon_greedy(vec![Box::new(StochasticBandit::greedy(2))], 0.1).label(),
+            "meta [greedy]"
+        );
+    }
+
+    #[test]
+    fn gradient_bandit() {
+        let mut bandit = StochasticBandit::gradient(2, 0.5).with_baseline(Baseline::Constant(0.0));
+
+        bandit.state.selected_arm = 0;
+        bandit.receive_reward(1.0);
+
+        // the preference of the rewarded arm increases, while the other one decreases
+        match &bandit.algorithm {
+            BanditAlgorithm::Gradient(gradient) => {
+                assert_eq!(gradient.preferences, vec![0.25, -0.25]);
+            }
+            _ => unreachable!(),
+        }
+
+        // the first reward equals the running mean, so the preferences do not change
+        let mut bandit = StochasticBandit::gradient(2, 0.5);
+        bandit.state.selected_arm = 0;
+        bandit.receive_reward(1.0);
+
+        match &bandit.algorithm {
+            BanditAlgorithm::Gradient(gradient) => {
+                assert_eq!(gradient.preferences, vec![0.0, 0.0]);
+                assert_eq!(gradient.average_reward, 1.0);
+            }
+            _ => unreachable!(),
+        }
+
+        bandit.restart();
+        match &bandit.algorithm {
+            BanditAlgorithm::Gradient(gradient) => {
+                assert_eq!(gradient.preferences, vec![0.0, 0.0]);
+                assert_eq!(gradient.average_reward, 0.0);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn thompson_sampling_bandit() {
+        let mut bandit = StochasticBandit::thompson_sampling(2).with_biased_state(1.0);
+        assert_eq!(gaussian_posterior(&bandit.state), vec![(1.0, 1.0); 2]);
+
+        for reward in [3.0, 5.0, 7.0] {
+            bandit.state.selected_arm = 1;
+            bandit.receive_reward(reward);
+        }
+
+        // the prior acts as a single observation of the initial value
+        let posterior = gaussian_posterior(&bandit.state);
+        assert_eq!(posterior[0], (1.0, 1.0));
+        assert_eq!(posterior[1], (4.0, 1.0));
+
+        // the posterior of the second arm dominates, so it is selected almost always
+        let selections: usize = (0..100).map(|_| bandit.select_arm()).sum();
+        assert!(selections > 90);
+
+        bandit.restart();
+        assert_eq!(gaussian_posterior(&bandit.state), vec![(1.0, 1.0); 2]);
+    }
+
+    #[test]
+    fn softmax_bandit() {
+        let mut bandit = StochasticBandit::softmax(3, 0.01);
+        assert_eq!(bandit.label(), "softmax (τ=0.01)");
+
+        bandit.state.estimated_arm_values = vec![0.0, 1000.0, 999.0];
+        bandit.state.steps = 1;
+
+        // a low temperature approximates the greedy bandit without overflowing
+        assert!((0..100).all(|_| bandit.select_arm() == 1));
+
+        // a high temperature makes the selection nearly uniform
+        let mut bandit = StochasticBandit::softmax(2, 1e6);
+        bandit.state.estimated_arm_values = vec![0.0, 1.0];
+        bandit.state.steps = 1;
+        let selections: usize = (0..1000).map(|_| bandit.select_arm()).sum();
+        assert!(selections > 400 && selections < 600);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid temperature value: 0")]
+    fn zero_temperature() {
+        StochasticBandit::softmax(2, 0.0);
+    }
+
+    #[test]
+    fn seeded_bandits() {
+        let selections = |seed: u64| -> Vec<usize> {
+            let mut bandit = StochasticBandit::epsilon_greedy(10, 0.5)
+                .with_rng(StdRng::seed_from_u64(seed))
+                .with_biased_state(1.0);
+            (0..100)
+                .map(|step| {
+                    let arm = bandit.select_arm();
+                    bandit.receive_reward(step as f64);
+                    arm
+                })
+                .collect()
+        };
+
+        assert_eq!(selections(42), selections(42));
+        assert_ne!(selections(42), selections(7));
+    }
+
+    #[test]
+    fn exp3() {
+        let mut bandit = Exp3::new(2, 0.1);
+        assert_eq!(bandit.probabilities(), vec![0.5; 2]);
+        assert_eq!(bandit.label(), "EXP3 (γ=0.1)");
+
+        // the second arm always pays off
+        let multi_arm = MultiArm::new(vec![
+            ScriptedArm::new(vec![0.0], ScriptEnd::Clamp),
+            ScriptedArm::new(vec![1.0], ScriptEnd::Clamp),
+        ]);
+        multi_arm.run(&mut bandit, 1000, &mut StdRng::seed_from_u64(0));
+        assert!(bandit.probabilities()[1] > 0.9);
+        assert_eq!(bandit.best_arm(), Some(1));
+
+        // exploration keeps every arm selectable
+        assert!(bandit.probabilities()[0] >= 0.05);
+
+        bandit.restart();
+        assert_eq!(bandit.probabilities(), vec![0.5; 2]);
+
+        // a seeded generator makes the arm selections reproducible
+        let selections = |seed: u64| -> Vec<usize> {
+            let mut bandit = Exp3::new(10, 0.5).with_rng(StdRng::seed_from_u64(seed));
+            (0..100).map(|_| bandit.select_arm()).collect()
+        };
+        assert_eq!(selections(42), selections(42));
+        assert_ne!(selections(42), selections(7));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid gamma value: 0")]
+    fn exp3_without_exploration() {
+        Exp3::new(2, 0.0);
+    }
+
+    #[test]
+    fn hedge() {
+        let mut hedge = Hedge::new(3, 0.5);
+        assert_eq!(hedge.probabilities(), vec![1.0 / 3.0; 3]);
+
+        for _ in 0..100 {
+            hedge.update(&[0.0, 1.0, 0.5]);
+        }
+
+        // the weights concentrate on the arm having the highest cumulative reward
+        let probabilities = hedge.probabilities();
+        assert!(probabilities[1] > 0.99);
+        assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert_eq!(hedge.select_arm(), 1);
+
+        hedge.restart();
+        assert_eq!(hedge.probabilities(), vec![1.0 / 3.0; 3]);
+
+        // a seeded generator makes the arm selections reproducible
+        let selections = |seed: u64| -> Vec<usize> {
+            let mut hedge = Hedge::new(10, 0.5).with_rng(StdRng::seed_from_u64(seed));
+            (0..100).map(|_| hedge.select_arm()).collect()
+        };
+        assert_eq!(selections(42), selections(42));
+        assert_ne!(selections(42), selections(7));
+    }
 }
@@ -1,13 +1,282 @@
 use crate::bandits::arm::{Arm, MultiArm};
 use crate::bandits::bandit::Bandit;
+#[cfg(feature = "plot")]
+use plotters::prelude::*;
+use rand::RngCore;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+#[cfg(feature = "plot")]
+use std::path::Path;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BenchmarkResult {
+    /// Labels describing each bandit, in the order the bandits were benchmarked.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub labels: Vec<String>,
     /// Average reward history is the average reward for each step across N runs.
     pub average_reward_history: Vec<Vec<f64>>,
+    /// Reward standard deviation history is the sample standard deviation of the reward for
+    /// each step across N runs, e.g., for error bars around the average reward. Note that it
+    /// is zero if there are fewer than two runs.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reward_std_history: Vec<Vec<f64>>,
     /// Optimal action history is the percentage of steps where each bandit chose the optimal action.
     /// Note that this statistic is measured only if the true value of each arm is provided.
     pub optimal_action_percentage_history: Option<Vec<Vec<f64>>>,
+    /// Average regret history is the cumulative regret for each step, that is, the sum of the
+    /// differences between the optimal arm value and the received rewards so far, averaged
+    /// across N runs. Note that this statistic is measured only if the true value of each arm
+    /// is provided.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub average_regret_history: Option<Vec<Vec<f64>>>,
+    /// Final rewards are the rewards each bandit received at the last step of every run.
+    /// Note that these raw rewards are retained only by [`Benchmark::run_with_final_rewards`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub final_rewards: Option<Vec<Vec<f64>>>,
+    /// Identified optimal arm indicates, for each bandit and run, whether the arm recommended
+    /// by the bandit at the end of the run was the optimal one. Note that this statistic is
+    /// measured only if the true value of each arm is provided.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub identified_optimal_arm: Option<Vec<Vec<bool>>>,
+}
+
+impl BenchmarkResult {
+    /// Returns the regret of each bandit against the best fixed arm in hindsight, that is,
+    /// `max_k sum_t r_k,t - sum_t r_t`, where `r_k,t` is the reward arm `k` would have given
+    /// at step `t` and `r_t` the average reward of the bandit. In contrast to the regret
+    /// against the true arm values, it is the appropriate measure for adversarial bandits,
+    /// such as EXP3 and Hedge, where rewards need not follow a fixed distribution.
+    ///
+    /// The counterfactual rewards of the arms are not observed by the benchmark, and thus
+    /// should be logged separately, e.g., by a simulation that reveals the rewards of all arms.
+    /// Since a single reward sequence is compared against the reward averaged across runs, the
+    /// measure is meaningful only for a benchmark of a single run, or for deterministic arms,
+    /// e.g., scripted ones, that yield the same rewards in every run.
+    ///
+    /// - `reward_log` - the reward of each arm at each step, indexed by arm and then by step.
+    ///   Every row must have exactly one reward per benchmark step.
+    pub fn hindsight_regret(&self, reward_log: &[Vec<f64>]) -> Vec<f64> {
+        let steps = self.average_reward_history.first().map_or(0, Vec::len);
+        if let Some(rewards) = reward_log.iter().find(|rewards| rewards.len() != steps) {
+            panic!("Invalid reward log length: {}", rewards.len());
+        }
+
+        let best_fixed_arm_reward = reward_log
+            .iter()
+            .map(|rewards| rewards.iter().sum::<f64>())
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        self.average_reward_history
+            .iter()
+            .map(|rewards| best_fixed_arm_reward - rewards.iter().sum::<f64>())
+            .collect()
+    }
+
+    /// Returns the total reward each bandit accumulated over a run, that is, the area under its
+    /// average reward curve, e.g., for ranking the bandits.
+    pub fn cumulative_reward(&self) -> Vec<f64> {
+        self.average_reward_history
+            .iter()
+            .map(|rewards| rewards.iter().sum())
+            .collect()
+    }
+
+    /// Returns the running sum of the average reward of each bandit, indexed by bandit and
+    /// then by step.
+    pub fn cumulative_reward_history(&self) -> Vec<Vec<f64>> {
+        self.average_reward_history
+            .iter()
+            .map(|rewards| {
+                rewards
+                    .iter()
+                    .scan(0.0, |total, reward| {
+                        *total += reward;
+                        Some(*total)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the fraction of runs in which each bandit recommended the optimal arm at the end
+    /// of the run, or none if the optimal arm is unknown. In contrast to the reward history,
+    /// this is the measure of interest in best-arm identification, where the rewards collected
+    /// while exploring do not matter. Bandits making no recommendation are never correct.
+    pub fn best_arm_identification_rate(&self) -> Option<Vec<f64>> {
+        self.identified_optimal_arm.as_ref().map(|identified| {
+            identified
+                .iter()
+                .map(|runs| {
+                    runs.iter().filter(|&&correct| correct).count() as f64 / runs.len() as f64
+                })
+                .collect()
+        })
+    }
+
+    /// Returns the requested quantiles of the final-step rewards across runs for each bandit,
+    /// or none if the final rewards were not retained. The quantiles show the spread of the
+    /// final performance, e.g., a bad tail, which is hidden by the average reward history.
+    /// Quantiles are linearly interpolated between the closest ranks, and they are NaN for a
+    /// bandit having no final rewards, e.g., when the runs have no steps.
+    ///
+    /// - `quantiles` - the quantiles of interest, each one between 0 and 1.
+    pub fn final_reward_quantiles(&self, quantiles: &[f64]) -> Option<Vec<Vec<f64>>> {
+        if let Some(q) = quantiles.iter().find(|q| !(0.0..=1.0).contains(*q)) {
+            panic!("Invalid quantile value: {q}");
+        }
+
+        self.final_rewards.as_ref().map(|final_rewards| {
+            final_rewards
+                .iter()
+                .map(|rewards| {
+                    if rewards.is_empty() {
+                        return vec![f64::NAN; quantiles.len()];
+                    }
+
+                    let mut sorted = rewards.clone();
+                    sorted.sort_by(|a, b| a.total_cmp(b));
+
+                    quantiles
+                        .iter()
+                        .map(|q| {
+                            let rank = q * (sorted.len() - 1) as f64;
+                            let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+                            sorted[lower] + (rank - lower as f64) * (sorted[upper] - sorted[lower])
+                        })
+                        .collect()
+                })
+                .collect()
+        })
+    }
+
+    /// Writes the benchmark result as CSV into the given writer, having one row per step. The
+    /// header names the metric and the bandit index of each column, e.g., `reward_0`, followed
+    /// by the optimal action percentage columns, e.g., `optimal_action_0`, if these are present.
+    ///
+    /// - `writer` - the writer that receives the CSV rows.
+    pub fn to_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        let mut columns: Vec<&Vec<f64>> = self.average_reward_history.iter().collect();
+        let mut header = vec![String::from("step")];
+        header.extend((0..self.average_reward_history.len()).map(|i| format!("reward_{i}")));
+        if let Some(optimal_actions) = &self.optimal_action_percentage_history {
+            columns.extend(optimal_actions);
+            header.extend((0..optimal_actions.len()).map(|i| format!("optimal_action_{i}")));
+        }
+        writeln!(writer, "{}", header.join(","))?;
+
+        let steps = columns.iter().map(|column| column.len()).max().unwrap_or(0);
+        for step in 0..steps {
+            let row: Vec<String> = columns
+                .iter()
+                .map(|column| column.get(step).map(f64::to_string).unwrap_or_default())
+                .collect();
+            writeln!(writer, "{step},{}", row.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl BenchmarkResult {
+    /// Serializes the benchmark result as JSON into the given writer. In contrast to a flat
+    /// format, JSON preserves the nested per-bandit histories as well as the optional ones.
+    ///
+    /// - `writer` - the writer that receives the JSON document.
+    pub fn to_json_writer<W: Write>(&self, writer: W) -> serde_json::Result<()> {
+        serde_json::to_writer(writer, self)
+    }
+}
+
+#[cfg(feature = "plot")]
+impl BenchmarkResult {
+    /// Draws the average reward of each bandit over the steps as a PNG image.
+    ///
+    /// - `path` - the path of the image file.
+    /// - `labels` - the legend entry of each bandit. Missing entries fall back to the labels
+    ///   of the result.
+    pub fn plot_reward(
+        &self,
+        path: &Path,
+        labels: &[&str],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.plot_histories(path, "Average reward", &self.average_reward_history, labels)
+    }
+
+    /// Draws the optimal action percentage of each bandit over the steps as a PNG image. Note
+    /// that it fails if the optimal action percentage was not measured.
+    ///
+    /// - `path` - the path of the image file.
+    /// - `labels` - the legend entry of each bandit. Missing entries fall back to the labels
+    ///   of the result.
+    pub fn plot_optimal_action(
+        &self,
+        path: &Path,
+        labels: &[&str],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let histories = self
+            .optimal_action_percentage_history
+            .as_ref()
+            .ok_or("Optimal action percentage history is not available")?;
+        self.plot_histories(path, "% Optimal action", histories, labels)
+    }
+
+    /// Draws one line per history over the steps.
+    fn plot_histories(
+        &self,
+        path: &Path,
+        description: &str,
+        histories: &[Vec<f64>],
+        labels: &[&str],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let steps = histories.iter().map(Vec::len).max().unwrap_or(0);
+        let (mut min, mut max) = histories
+            .iter()
+            .flatten()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            });
+        if min > max {
+            (min, max) = (0.0, 1.0);
+        } else if min == max {
+            (min, max) = (min - 0.5, max + 0.5);
+        }
+
+        let root = BitMapBackend::new(path, (1024, 768)).into_drawing_area();
+        root.fill(&WHITE)?;
+        let mut chart = ChartBuilder::on(&root)
+            .margin(20)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(0..steps.max(1), min..max)?;
+        chart
+            .configure_mesh()
+            .x_desc("Steps")
+            .y_desc(description)
+            .draw()?;
+
+        for (i, history) in histories.iter().enumerate() {
+            let label = labels
+                .get(i)
+                .map(|label| label.to_string())
+                .or_else(|| self.labels.get(i).cloned())
+                .unwrap_or_else(|| format!("bandit {i}"));
+            let color = Palette99::pick(i).to_rgba();
+            chart
+                .draw_series(LineSeries::new(history.iter().copied().enumerate(), color))?
+                .label(label)
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+        }
+
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+        root.present()?;
+        Ok(())
+    }
 }
 
 pub struct Benchmark<A: Arm> {
@@ -22,54 +291,288 @@ impl<A: Arm> Benchmark<A> {
     /// - `runs` - the number of repeated runs.
     /// - `steps` - the number of steps per run.
     pub fn run(&mut self, runs: usize, steps: usize) -> BenchmarkResult {
-        // find optimal arm
-        let optimal_arm = self.arm.optimal_arm();
+        self.run_with_rng(runs, steps, &mut rand::thread_rng())
+    }
+
+    /// Runs a benchmark on the provided bandits, exactly as [`Benchmark::run`], but samples the
+    /// rewards of the arms using the given random number generator. Along with bandits having
+    /// seeded generators, see [`StochasticBandit::with_rng`], a seeded generator makes the
+    /// whole benchmark reproducible.
+    ///
+    /// - `runs` - the number of repeated runs.
+    /// - `steps` - the number of steps per run.
+    /// - `rng` - the random number generator used to pull the arms.
+    ///
+    /// [`StochasticBandit::with_rng`]: crate::bandits::bandit::StochasticBandit::with_rng
+    pub fn run_with_rng(
+        &mut self,
+        runs: usize,
+        steps: usize,
+        rng: &mut dyn RngCore,
+    ) -> BenchmarkResult {
+        self.execute(runs, steps, false, rng, |_, _, _, _| {})
+    }
+
+    /// Runs a benchmark on the provided bandits, exactly as [`Benchmark::run`], but also
+    /// retains the final-step reward of each run, so that the distribution of the final
+    /// performance can be inspected using [`BenchmarkResult::final_reward_quantiles`].
+    ///
+    /// - `runs` - the number of repeated runs.
+    /// - `steps` - the number of steps per run.
+    pub fn run_with_final_rewards(&mut self, runs: usize, steps: usize) -> BenchmarkResult {
+        self.run_with_final_rewards_and_rng(runs, steps, &mut rand::thread_rng())
+    }
+
+    /// Runs a benchmark on the provided bandits, exactly as
+    /// [`Benchmark::run_with_final_rewards`], but samples the rewards of the arms using the
+    /// given random number generator.
+    ///
+    /// - `runs` - the number of repeated runs.
+    /// - `steps` - the number of steps per run.
+    /// - `rng` - the random number generator used to pull the arms.
+    pub fn run_with_final_rewards_and_rng(
+        &mut self,
+        runs: usize,
+        steps: usize,
+        rng: &mut dyn RngCore,
+    ) -> BenchmarkResult {
+        self.execute(runs, steps, true, rng, |_, _, _, _| {})
+    }
+
+    /// Runs a benchmark on the provided bandits, similar to [`Benchmark::run`], but builds a
+    /// fresh bandit problem at the start of each run, e.g., the 10-armed testbed of Sutton and
+    /// Barto, which draws the value of each arm from a standard normal distribution. The
+    /// optimal action percentage is measured against the optimal arm of each run. The original
+    /// arms are restored when the benchmark completes.
+    ///
+    /// - `runs` - the number of repeated runs.
+    /// - `steps` - the number of steps per run.
+    /// - `arm_factory` - a function building the arms of a run from a random number generator.
+    pub fn run_randomized<F>(
+        &mut self,
+        runs: usize,
+        steps: usize,
+        arm_factory: F,
+    ) -> BenchmarkResult
+    where
+        F: Fn(&mut dyn RngCore) -> MultiArm<A>,
+    {
+        self.run_randomized_with_rng(runs, steps, arm_factory, &mut rand::thread_rng())
+    }
+
+    /// Runs a benchmark on the provided bandits, exactly as [`Benchmark::run_randomized`], but
+    /// builds the arms and samples their rewards using the given random number generator.
+    ///
+    /// - `runs` - the number of repeated runs.
+    /// - `steps` - the number of steps per run.
+    /// - `arm_factory` - a function building the arms of a run from a random number generator.
+    /// - `rng` - the random number generator used to build and pull the arms.
+    pub fn run_randomized_with_rng<F>(
+        &mut self,
+        runs: usize,
+        steps: usize,
+        arm_factory: F,
+        rng: &mut dyn RngCore,
+    ) -> BenchmarkResult
+    where
+        F: Fn(&mut dyn RngCore) -> MultiArm<A>,
+    {
+        let original_arm = std::mem::replace(&mut self.arm, MultiArm::new(Vec::new()));
+
+        // every bandit of a run faces the same arms
+        let result = self.execute(runs, steps, false, rng, |i, t, multi_arm, rng| {
+            if i == 0 && t == 0 {
+                *multi_arm = arm_factory(rng);
+            }
+        });
+
+        self.arm = original_arm;
+        result
+    }
+
+    /// Runs the benchmark, calling `before_step` with the bandit, the step, the arms and the
+    /// random number generator before every step of every bandit and run, so that the arms
+    /// can be modified during a run. Within a run, each bandit plays all its steps in turn,
+    /// starting from arms reset to their initial state, so that arms whose rewards depend on
+    /// the previous pulls, e.g., seasonal arms, advance once per step for every bandit. The
+    /// optimal arm is found anew at each step. The final rewards of the runs are retained only
+    /// if requested. The arms are pulled using the given random number generator.
+    fn execute<F>(
+        &mut self,
+        runs: usize,
+        steps: usize,
+        retain_final_rewards: bool,
+        rng: &mut dyn RngCore,
+        mut before_step: F,
+    ) -> BenchmarkResult
+    where
+        F: FnMut(usize, usize, &mut MultiArm<A>, &mut dyn RngCore),
+    {
+        // the optimal action statistic is measured only if the optimal arm is always known
+        let mut optimal_arm_known = true;
 
         // average reward and optimal actions statistics across runs
         let mut average_reward_history = vec![vec![0.0; steps]; self.bandits.len()];
         let mut optimal_action_percentage_history = vec![vec![0.0; steps]; self.bandits.len()];
+        let mut average_regret_history = vec![vec![0.0; steps]; self.bandits.len()];
+        let mut final_rewards = vec![Vec::with_capacity(runs); self.bandits.len()];
+        let mut identified_optimal_arm = vec![Vec::with_capacity(runs); self.bandits.len()];
+
+        // sum of squared deviations from the average reward, updated online using Welford's method
+        let mut reward_m2_history = vec![vec![0.0; steps]; self.bandits.len()];
 
         // run the benchmark
-        for _ in 0..runs {
-            // restart all bandits
-            self.bandits.iter_mut().for_each(|bandit| bandit.restart());
+        for run in 0..runs {
+            for (i, bandit) in self.bandits.iter_mut().enumerate() {
+                bandit.restart();
+                let mut cumulative_regret = 0.0;
+
+                for t in 0..steps {
+                    // update the arms, which every bandit faces from their initial state
+                    before_step(i, t, &mut self.arm, rng);
+                    if t == 0 {
+                        self.arm.reset();
+                    }
+
+                    // find the optimal arm
+                    let optimal_arm = self.arm.optimal_arm();
+                    let optimal_value = self.arm.optimal_value().unwrap_or(f64::NAN);
+                    optimal_arm_known &= optimal_arm.is_some();
 
-            for t in 0..steps {
-                for (i, bandit) in self.bandits.iter_mut().enumerate() {
                     let arm = bandit.select_arm();
-                    let reward = self.arm.pull(arm);
-                    average_reward_history[i][t] += reward;
+                    let reward = self.arm.pull_with(arm, rng);
+                    let delta = reward - average_reward_history[i][t];
+                    average_reward_history[i][t] += delta / (run + 1) as f64;
+                    reward_m2_history[i][t] += delta * (reward - average_reward_history[i][t]);
                     if optimal_arm.map(|j| j == arm).unwrap_or(false) {
                         optimal_action_percentage_history[i][t] += 1.0;
                     }
+                    cumulative_regret += optimal_value - reward;
+                    average_regret_history[i][t] += cumulative_regret;
+                    if t + 1 == steps && retain_final_rewards {
+                        final_rewards[i].push(reward);
+                    }
                     bandit.receive_reward(reward);
                 }
+
+                // check whether the bandit identified the optimal arm by the end of the run
+                let optimal_arm = self.arm.optimal_arm();
+                optimal_arm_known &= optimal_arm.is_some();
+                identified_optimal_arm[i]
+                    .push(optimal_arm.is_some() && bandit.best_arm() == optimal_arm);
             }
         }
 
         // average results over the number of runs
+        let mut reward_std_history = vec![vec![0.0; steps]; self.bandits.len()];
         for t in 0..steps {
             for i in 0..self.bandits.len() {
-                average_reward_history[i][t] /= runs as f64;
+                if runs > 1 {
+                    reward_std_history[i][t] = (reward_m2_history[i][t] / (runs - 1) as f64).sqrt();
+                }
                 optimal_action_percentage_history[i][t] /= runs as f64;
+                average_regret_history[i][t] /= runs as f64;
             }
         }
 
         BenchmarkResult {
+            labels: self.bandits.iter().map(|bandit| bandit.label()).collect(),
             average_reward_history,
-            optimal_action_percentage_history: optimal_arm
-                .map(|_| optimal_action_percentage_history),
+            reward_std_history,
+            optimal_action_percentage_history: if optimal_arm_known {
+                Some(optimal_action_percentage_history)
+            } else {
+                None
+            },
+            average_regret_history: if optimal_arm_known {
+                Some(average_regret_history)
+            } else {
+                None
+            },
+            final_rewards: if retain_final_rewards {
+                Some(final_rewards)
+            } else {
+                None
+            },
+            identified_optimal_arm: if optimal_arm_known {
+                Some(identified_optimal_arm)
+            } else {
+                None
+            },
         }
     }
 }
 
+impl<A: Arm + Clone> Benchmark<A> {
+    /// Runs a benchmark on the provided bandits, similar to [`Benchmark::run`], but replaces
+    /// arms at scheduled steps within each run. Every run starts from the original arms, which
+    /// are restored when the benchmark completes. The benchmark shows how quickly bandits
+    /// recover from a distribution shift.
+    ///
+    /// - `runs` - the number of repeated runs.
+    /// - `steps` - the number of steps per run.
+    /// - `changes` - a list of (step, arm index, new arm) triplets.
+    pub fn run_with_schedule(
+        &mut self,
+        runs: usize,
+        steps: usize,
+        changes: Vec<(usize, usize, A)>,
+    ) -> BenchmarkResult {
+        self.run_with_schedule_and_rng(runs, steps, changes, &mut rand::thread_rng())
+    }
+
+    /// Runs a benchmark on the provided bandits, exactly as [`Benchmark::run_with_schedule`],
+    /// but samples the rewards of the arms using the given random number generator.
+    ///
+    /// - `runs` - the number of repeated runs.
+    /// - `steps` - the number of steps per run.
+    /// - `changes` - a list of (step, arm index, new arm) triplets.
+    /// - `rng` - the random number generator used to pull the arms.
+    pub fn run_with_schedule_and_rng(
+        &mut self,
+        runs: usize,
+        steps: usize,
+        changes: Vec<(usize, usize, A)>,
+        rng: &mut dyn RngCore,
+    ) -> BenchmarkResult {
+        let original_arm = self.arm.clone();
+
+        let result = self.execute(runs, steps, false, rng, |_, t, multi_arm, _| {
+            if t == 0 {
+                *multi_arm = original_arm.clone();
+            }
+            for (_, k, arm) in changes.iter().filter(|(step, _, _)| *step == t) {
+                multi_arm.set_arm(*k, arm.clone());
+            }
+        });
+
+        self.arm = original_arm;
+        result
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bandits::arm::RandomArm;
-    use crate::bandits::bandit::StochasticBandit;
+    use crate::bandits::arm::{RandomArm, ScriptEnd, ScriptedArm};
+    use crate::bandits::bandit::{Schedule, StochasticBandit};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
     use rand_distr::{Distribution, Normal};
 
+    /// A bandit that always selects the first arm.
+    struct Stubborn;
+
+    impl Bandit for Stubborn {
+        fn select_arm(&mut self) -> usize {
+            0
+        }
+
+        fn receive_reward(&mut self, _: f64) {}
+
+        fn restart(&mut self) {}
+    }
+
     #[test]
     fn test() {
         let multi_arm = MultiArm::new(
@@ -87,7 +590,413 @@ mod tests {
         }
         .run(10, 100);
 
+        assert_eq!(result.labels, vec!["greedy"]);
         assert_eq!(result.average_reward_history.len(), 1);
         assert!(result.optimal_action_percentage_history.is_some());
     }
+
+    #[test]
+    fn scheduled_benchmark() {
+        let mut benchmark = Benchmark {
+            arm: MultiArm::deterministic(&[1.0, 0.0]),
+            bandits: vec![Box::new(Stubborn)],
+        };
+        let worst_arm = RandomArm::from_distribution(Some(-1.0), Normal::new(-1.0, 0.0).unwrap());
+        let result = benchmark.run_with_schedule(3, 20, vec![(10, 0, worst_arm)]);
+
+        // the first arm is optimal until it becomes the worst arm
+        let rewards = &result.average_reward_history[0];
+        let optimal_actions = &result.optimal_action_percentage_history.unwrap()[0];
+        assert!(rewards[..10].iter().all(|&reward| reward == 1.0));
+        assert!(rewards[10..].iter().all(|&reward| reward == -1.0));
+        assert!(optimal_actions[..10].iter().all(|&p| p == 1.0));
+        assert!(optimal_actions[10..].iter().all(|&p| p == 0.0));
+
+        // the original arms are restored
+        assert_eq!(benchmark.arm.pull(0), 1.0);
+
+        // a seeded generator yields the same rewards after the change
+        let mut run = |seed| {
+            benchmark
+                .run_with_schedule_and_rng(
+                    3,
+                    20,
+                    vec![(10, 0, RandomArm::normal(-1.0))],
+                    &mut StdRng::seed_from_u64(seed),
+                )
+                .average_reward_history
+        };
+        assert_eq!(run(5), run(5));
+    }
+
+    #[test]
+    fn non_stationary_benchmark() {
+        // each bandit of each run faces the scripted rewards from the start
+        let result = Benchmark {
+            arm: MultiArm::new(vec![ScriptedArm::new(
+                vec![1.0, 2.0, 3.0],
+                ScriptEnd::Clamp,
+            )]),
+            bandits: vec![Box::new(Stubborn), Box::new(Stubborn)],
+        }
+        .run(2, 3);
+        assert_eq!(result.average_reward_history, vec![vec![1.0, 2.0, 3.0]; 2]);
+    }
+
+    #[test]
+    fn randomized_benchmark() {
+        let mut benchmark = Benchmark {
+            arm: MultiArm::deterministic(&[1.0, 0.0]),
+            bandits: vec![Box::new(Stubborn)],
+        };
+        let result = benchmark.run_randomized(1000, 1, |rng| {
+            let normal = Normal::new(0.0, 1.0).unwrap();
+            MultiArm::new(
+                (0..10)
+                    .map(|_| RandomArm::normal(normal.sample(rng)))
+                    .collect(),
+            )
+        });
+
+        // the first arm is optimal in about one out of ten testbeds
+        let optimal_actions = &result.optimal_action_percentage_history.unwrap()[0];
+        assert!((optimal_actions[0] - 0.1).abs() < 0.05);
+
+        // the original arms are restored
+        assert_eq!(benchmark.arm.pull(0), 1.0);
+
+        // a seeded generator builds the same testbeds and yields the same rewards
+        let mut run = |seed| {
+            benchmark.run_randomized_with_rng(
+                5,
+                10,
+                |rng| MultiArm::new((0..10).map(|_| RandomArm::normal(rng.gen())).collect()),
+                &mut StdRng::seed_from_u64(seed),
+            )
+        };
+        let (first, second) = (run(7), run(7));
+        assert_eq!(first.average_reward_history, second.average_reward_history);
+        assert_ne!(first.average_reward_history, run(8).average_reward_history);
+    }
+
+    #[test]
+    fn deterministic_benchmark() {
+        let result = Benchmark {
+            arm: MultiArm::deterministic(&[1.0, 2.0]),
+            bandits: vec![Box::new(StochasticBandit::greedy(2).with_biased_state(5.0))],
+        }
+        .run(10, 50);
+
+        // the optimistic bandit tries both arms once and then always exploits the best arm
+        let rewards = &result.average_reward_history[0];
+        let optimal_actions = &result.optimal_action_percentage_history.unwrap()[0];
+        assert!((rewards[0] + rewards[1] - 3.0).abs() < 1e-9);
+        assert!(rewards[2..].iter().all(|&reward| reward == 2.0));
+        assert!((optimal_actions[0] + optimal_actions[1] - 1.0).abs() < 1e-9);
+        assert!(optimal_actions[2..].iter().all(|&p| p == 1.0));
+    }
+
+    #[test]
+    fn cumulative_reward() {
+        let result = Benchmark {
+            arm: MultiArm::deterministic(&[0.5, 0.25]),
+            bandits: vec![Box::new(Stubborn)],
+        }
+        .run(5, 40);
+
+        // the stubborn bandit always collects the constant reward of the first arm
+        assert_eq!(result.cumulative_reward(), vec![40.0 * 0.5]);
+        let history = &result.cumulative_reward_history()[0];
+        assert_eq!(history.len(), 40);
+        assert_eq!(history[0], 0.5);
+        assert_eq!(history[39], 40.0 * 0.5);
+    }
+
+    #[test]
+    fn reward_std() {
+        let result = Benchmark {
+            arm: MultiArm::deterministic(&[1.0, 2.0]),
+            bandits: vec![Box::new(Stubborn)],
+        }
+        .run(10, 20);
+
+        // a constant reward has no spread across runs
+        assert_eq!(result.reward_std_history, vec![vec![0.0; 20]]);
+
+        let result = Benchmark {
+            arm: MultiArm::new(vec![RandomArm::normal(0.0)]),
+            bandits: vec![Box::new(Stubborn)],
+        }
+        .run(2000, 2);
+
+        // unit normal rewards have unit standard deviation
+        let std = &result.reward_std_history[0];
+        assert!(std.iter().all(|&std| (std - 1.0).abs() < 0.1));
+    }
+
+    #[test]
+    fn final_reward_quantiles() {
+        let result = Benchmark {
+            arm: MultiArm::deterministic(&[1.0, 2.0]),
+            bandits: vec![Box::new(Stubborn)],
+        }
+        .run_with_final_rewards(4, 10);
+
+        assert_eq!(result.final_rewards, Some(vec![vec![1.0; 4]]));
+        assert_eq!(
+            result.final_reward_quantiles(&[0.0, 0.5, 1.0]),
+            Some(vec![vec![1.0; 3]])
+        );
+
+        // quantiles are interpolated between the closest ranks
+        let result = BenchmarkResult {
+            final_rewards: Some(vec![vec![4.0, 1.0, 3.0, 2.0]]),
+            ..result
+        };
+        assert_eq!(
+            result.final_reward_quantiles(&[0.0, 0.5, 0.25, 1.0]),
+            Some(vec![vec![1.0, 2.5, 1.75, 4.0]])
+        );
+
+        // the final rewards are not retained by default
+        let result = Benchmark {
+            arm: MultiArm::deterministic(&[1.0, 2.0]),
+            bandits: vec![Box::new(Stubborn)],
+        }
+        .run(4, 10);
+        assert_eq!(result.final_reward_quantiles(&[0.5]), None);
+
+        // runs without steps have no final rewards
+        let result = Benchmark {
+            arm: MultiArm::deterministic(&[1.0, 2.0]),
+            bandits: vec![Box::new(Stubborn)],
+        }
+        .run_with_final_rewards(5, 0);
+        let quantiles = result.final_reward_quantiles(&[0.5]).unwrap();
+        assert!(quantiles[0][0].is_nan());
+
+        // a seeded generator yields the same final rewards
+        let mut benchmark = Benchmark {
+            arm: MultiArm::new(vec![RandomArm::normal(0.0)]),
+            bandits: vec![Box::new(Stubborn)],
+        };
+        let mut run = |seed| {
+            benchmark
+                .run_with_final_rewards_and_rng(5, 10, &mut StdRng::seed_from_u64(seed))
+                .final_rewards
+        };
+        assert_eq!(run(3), run(3));
+    }
+
+    #[test]
+    fn exploration_schedules() {
+        let result = Benchmark {
+            arm: MultiArm::deterministic(&[1.0, 2.0]),
+            bandits: vec![
+                Box::new(StochasticBandit::epsilon_greedy(2, 0.1)),
+                Box::new(
+                    StochasticBandit::epsilon_greedy_with_schedule(
+                        2,
+                        Schedule::InverseDecay {
+                            initial: 1.0,
+                            decay: 1.0,
+                        },
+                    )
+                    .with_biased_state(5.0),
+                ),
+            ],
+        }
+        .run(20, 500);
+
+        assert_eq!(
+            result.labels,
+            vec!["ε-greedy (ε=0.1)", "ε-greedy (ε=1/(1+t))"]
+        );
+
+        // the decaying bandit eventually exploits the optimal arm as the greedy bandit would
+        let optimal_actions = &result.optimal_action_percentage_history.unwrap()[1];
+        assert!(optimal_actions[400..].iter().sum::<f64>() / 100.0 > 0.98);
+    }
+
+    #[test]
+    fn regret() {
+        let result = Benchmark {
+            arm: MultiArm::deterministic(&[1.0, 10.0]),
+            bandits: vec![Box::new(StochasticBandit::greedy(2))],
+        }
+        .run(1, 100);
+
+        // the regret of a greedy bandit accumulates whenever it gets stuck on the worse arm
+        let regret = &result.average_regret_history.unwrap()[0];
+        assert!(regret.windows(2).all(|w| w[0] <= w[1]));
+        assert!(regret[99] == 0.0 || regret[99] == 900.0);
+
+        // the regret is unknown without the true arm values
+        let arms = vec![RandomArm::from_distribution(
+            None,
+            Normal::new(0.0, 1.0).unwrap(),
+        )];
+        let result = Benchmark {
+            arm: MultiArm::new(arms),
+            bandits: vec![Box::new(StochasticBandit::greedy(1))],
+        }
+        .run(1, 10);
+        assert!(result.average_regret_history.is_none());
+    }
+
+    #[test]
+    fn best_arm_identification_rate() {
+        let result = Benchmark {
+            arm: MultiArm::deterministic(&[1.0, 2.0]),
+            bandits: vec![
+                Box::new(StochasticBandit::greedy(2).with_biased_state(5.0)),
+                Box::new(Stubborn),
+            ],
+        }
+        .run(5, 10);
+
+        // the optimistic bandit always identifies the optimal arm, while the stubborn one
+        // never recommends an arm
+        assert_eq!(result.best_arm_identification_rate(), Some(vec![1.0, 0.0]));
+    }
+
+    #[test]
+    fn hindsight_regret() {
+        let result = BenchmarkResult {
+            labels: vec![String::from("first"), String::from("second")],
+            average_reward_history: vec![vec![1.0, 0.0, 1.0], vec![0.0, 1.0, 1.0]],
+            reward_std_history: vec![vec![0.0; 3]; 2],
+            optimal_action_percentage_history: None,
+            average_regret_history: None,
+            final_rewards: None,
+            identified_optimal_arm: None,
+        };
+
+        // the second arm is the best in hindsight, having a total reward of 2
+        let reward_log = vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 1.0]];
+        assert_eq!(result.hindsight_regret(&reward_log), vec![0.0, 0.0]);
+
+        let reward_log = vec![vec![1.0, 1.0, 1.0], vec![0.0, 0.0, 0.0]];
+        assert_eq!(result.hindsight_regret(&reward_log), vec![1.0, 1.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid reward log length: 2")]
+    fn hindsight_regret_short_log() {
+        let result = BenchmarkResult {
+            labels: vec![String::from("first")],
+            average_reward_history: vec![vec![1.0, 0.0, 1.0]],
+            reward_std_history: vec![vec![0.0; 3]],
+            optimal_action_percentage_history: None,
+            average_regret_history: None,
+            final_rewards: None,
+            identified_optimal_arm: None,
+        };
+        result.hindsight_regret(&[vec![1.0, 0.0, 0.0], vec![0.0, 1.0]]);
+    }
+
+    #[test]
+    fn seeded_benchmark() {
+        let run = || {
+            Benchmark {
+                arm: MultiArm::new(vec![RandomArm::normal(0.0), RandomArm::normal(1.0)]),
+                bandits: vec![Box::new(
+                    StochasticBandit::epsilon_greedy(2, 0.1).with_rng(StdRng::seed_from_u64(7)),
+                )],
+            }
+            .run_with_rng(5, 50, &mut StdRng::seed_from_u64(11))
+        };
+
+        // seeding both the bandits and the arms makes the benchmark reproducible
+        assert_eq!(run().average_reward_history, run().average_reward_history);
+    }
+
+    #[test]
+    fn csv_export() {
+        let mut result = BenchmarkResult {
+            labels: vec![String::from("greedy"), String::from("UCB (c=2)")],
+            average_reward_history: vec![vec![0.5, 1.0], vec![0.25, 0.75]],
+            reward_std_history: vec![vec![0.0, 0.0], vec![0.0, 0.0]],
+            optimal_action_percentage_history: None,
+            average_regret_history: None,
+            final_rewards: None,
+            identified_optimal_arm: None,
+        };
+
+        let mut buffer = Vec::new();
+        result.to_csv(&mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "step,reward_0,reward_1\n0,0.5,0.25\n1,1,0.75\n"
+        );
+
+        result.optimal_action_percentage_history = Some(vec![vec![0.0, 1.0], vec![1.0, 1.0]]);
+        let mut buffer = Vec::new();
+        result.to_csv(&mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer)
+                .unwrap()
+                .lines()
+                .collect::<Vec<_>>(),
+            vec![
+                "step,reward_0,reward_1,optimal_action_0,optimal_action_1",
+                "0,0.5,0.25,0,1",
+                "1,1,0.75,1,1"
+            ]
+        );
+    }
+
+    #[cfg(feature = "plot")]
+    #[test]
+    fn plot() {
+        let result = BenchmarkResult {
+            labels: vec![String::from("greedy"), String::from("UCB (c=2)")],
+            average_reward_history: vec![vec![0.5, 1.0], vec![0.25, 0.75]],
+            reward_std_history: vec![vec![0.0, 0.0], vec![0.0, 0.0]],
+            optimal_action_percentage_history: None,
+            average_regret_history: None,
+            final_rewards: None,
+            identified_optimal_arm: None,
+        };
+
+        let path = std::env::temp_dir().join("readapt_plot_reward.png");
+        result.plot_reward(&path, &["greedy"]).unwrap();
+        assert!(std::fs::metadata(&path).unwrap().len() > 0);
+        std::fs::remove_file(&path).unwrap();
+
+        // the optimal action percentage is not measured
+        assert!(result.plot_optimal_action(&path, &[]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip() {
+        let result = BenchmarkResult {
+            labels: vec![String::from("greedy"), String::from("UCB (c=2)")],
+            average_reward_history: vec![vec![0.5, 1.0], vec![0.25, 0.75]],
+            reward_std_history: vec![vec![0.0, 0.0], vec![0.0, 0.0]],
+            optimal_action_percentage_history: None,
+            average_regret_history: None,
+            final_rewards: None,
+            identified_optimal_arm: None,
+        };
+
+        let mut buffer = Vec::new();
+        result.to_json_writer(&mut buffer).unwrap();
+        let restored: BenchmarkResult = serde_json::from_slice(&buffer).unwrap();
+
+        assert_eq!(
+            restored.average_reward_history,
+            result.average_reward_history
+        );
+        assert_eq!(restored.labels, result.labels);
+        assert_eq!(restored.optimal_action_percentage_history, None);
+
+        // results serialized before the labels were introduced are still readable
+        let restored: BenchmarkResult = serde_json::from_str(
+            r#"{"average_reward_history":[[0.5]],"optimal_action_percentage_history":null}"#,
+        )
+        .unwrap();
+        assert!(restored.labels.is_empty());
+    }
 }
@@ -0,0 +1,182 @@
+/// Contextual bandits observe a context, e.g., a feature vector describing a user, before
+/// selecting an arm at each step. In contrast to the context-free bandits, the best arm may
+/// thus differ from step to step.
+pub trait ContextualBandit {
+    /// Selects an arm to pull given the context of the current step.
+    fn select_arm(&mut self, context: &[f64]) -> usize;
+    /// Rewards the bandit for the arm selected in the last context.
+    fn receive_reward(&mut self, reward: f64);
+    /// Restarts the bandit by clearing the internal state.
+    fn restart(&mut self);
+}
+
+/// LinUCB assumes that the expected reward of each arm is linear in the context, that is,
+/// `θ_aᵀx`, and estimates the coefficients of each arm by ridge regression over the contexts
+/// and rewards observed so far. It selects the arm maximizing the upper confidence bound
+/// `θ_aᵀx + α √(xᵀA_a⁻¹x)`, where `A_a` is the regularized design matrix of the arm.
+///
+/// The inverse of each design matrix is maintained directly, using the Sherman-Morrison
+/// formula, so that no matrix inversion is needed per step.
+#[derive(Debug, Clone)]
+pub struct LinUcb {
+    dimension: usize,
+    alpha: f64,
+    selected_arm: usize,
+    context: Vec<f64>,
+    inverse_designs: Vec<Vec<Vec<f64>>>,
+    responses: Vec<Vec<f64>>,
+}
+
+impl LinUcb {
+    /// Creates a LinUCB contextual bandit.
+    ///
+    /// - `arms` - the number of available arms.
+    /// - `dimension` - the number of context features.
+    /// - `alpha` - the degree of exploration.
+    ///
+    /// # Example
+    ///```
+    /// use readapt::bandits::contextual::{ContextualBandit, LinUcb};
+    ///
+    /// let mut bandit = LinUcb::new(2, 3, 1.0);
+    /// let arm = bandit.select_arm(&[1.0, 0.0, 0.5]);
+    /// bandit.receive_reward(if arm == 0 { 1.0 } else { 0.0 });
+    ///```
+    pub fn new(arms: usize, dimension: usize, alpha: f64) -> Self {
+        if alpha < 0.0 {
+            panic!("Invalid alpha value: {alpha}");
+        }
+
+        LinUcb {
+            dimension,
+            alpha,
+            selected_arm: 0,
+            context: vec![0_f64; dimension],
+            inverse_designs: vec![identity(dimension); arms],
+            responses: vec![vec![0_f64; dimension]; arms],
+        }
+    }
+
+    /// Returns the estimated coefficients `θ_a = A_a⁻¹b_a` of an arm.
+    ///
+    /// - `arm` - the arm of interest.
+    pub fn coefficients(&self, arm: usize) -> Vec<f64> {
+        multiply(&self.inverse_designs[arm], &self.responses[arm])
+    }
+}
+
+impl ContextualBandit for LinUcb {
+    fn select_arm(&mut self, context: &[f64]) -> usize {
+        if context.len() != self.dimension {
+            panic!("Invalid context dimension: {}", context.len());
+        }
+
+        self.context = context.to_vec();
+        self.selected_arm = (0..self.inverse_designs.len())
+            .map(|arm| {
+                let variance = dot(context, &multiply(&self.inverse_designs[arm], context));
+                dot(&self.coefficients(arm), context) + self.alpha * variance.sqrt()
+            })
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+            .expect("Arms must not be empty");
+
+        self.selected_arm
+    }
+
+    fn receive_reward(&mut self, reward: f64) {
+        let x = &self.context;
+        let inverse = &mut self.inverse_designs[self.selected_arm];
+
+        // A⁻¹ ← A⁻¹ - (A⁻¹x)(A⁻¹x)ᵀ / (1 + xᵀA⁻¹x), since A is symmetric
+        let projection = multiply(inverse, x);
+        let denominator = 1.0 + dot(x, &projection);
+        for (i, row) in inverse.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value -= projection[i] * projection[j] / denominator;
+            }
+        }
+
+        for (response, feature) in self.responses[self.selected_arm].iter_mut().zip(x) {
+            *response += reward * feature;
+        }
+    }
+
+    fn restart(&mut self) {
+        let arms = self.inverse_designs.len();
+        self.selected_arm = 0;
+        self.context = vec![0_f64; self.dimension];
+        self.inverse_designs = vec![identity(self.dimension); arms];
+        self.responses = vec![vec![0_f64; self.dimension]; arms];
+    }
+}
+
+/// Returns the identity matrix of the given dimension.
+fn identity(dimension: usize) -> Vec<Vec<f64>> {
+    (0..dimension)
+        .map(|i| {
+            (0..dimension)
+                .map(|j| if i == j { 1.0 } else { 0.0 })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the product of a matrix and a vector.
+fn multiply(matrix: &[Vec<f64>], vector: &[f64]) -> Vec<f64> {
+    matrix.iter().map(|row| dot(row, vector)).collect()
+}
+
+/// Returns the dot product of two vectors.
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn lin_ucb_bandit() {
+        // the first arm pays off in the first context and the second arm in the second one
+        let contexts = [[1.0, 0.0], [0.0, 1.0]];
+        let mut bandit = LinUcb::new(2, 2, 0.5);
+        let mut rng = rand::thread_rng();
+        for _ in 0..500 {
+            let k = rng.gen_range(0..2);
+            let arm = bandit.select_arm(&contexts[k]);
+            bandit.receive_reward(if arm == k { 1.0 } else { 0.0 });
+        }
+
+        assert_eq!(bandit.select_arm(&contexts[0]), 0);
+        assert_eq!(bandit.select_arm(&contexts[1]), 1);
+        assert!((bandit.coefficients(0)[0] - 1.0).abs() < 0.1);
+        assert!(bandit.coefficients(1)[0].abs() < 0.1);
+
+        bandit.restart();
+        assert_eq!(bandit.coefficients(0), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn sherman_morrison_update() {
+        let mut bandit = LinUcb::new(1, 2, 1.0);
+        bandit.select_arm(&[1.0, 2.0]);
+        bandit.receive_reward(1.0);
+
+        // A = I + xxᵀ = [[2, 2], [2, 5]], whose inverse is [[5, -2], [-2, 2]] / 6
+        let expected = [[5.0 / 6.0, -2.0 / 6.0], [-2.0 / 6.0, 2.0 / 6.0]];
+        for (row, expected_row) in bandit.inverse_designs[0].iter().zip(expected) {
+            for (value, expected_value) in row.iter().zip(expected_row) {
+                assert!((value - expected_value).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid context dimension: 3")]
+    fn invalid_context() {
+        LinUcb::new(2, 2, 1.0).select_arm(&[1.0, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::bandits::arm::{Arm, MultiArm};
+use crate::bandits::bandit::{Bandit, Exp3, StochasticBandit};
+use crate::bandits::bench::{Benchmark, BenchmarkResult};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+/// Benchmarks one of each built-in bandit algorithm, using sensible default parameters,
+/// against the given arms. The result is labelled by algorithm, which makes the survey a
+/// quick way to get a feel for the relative performance of the algorithms on a problem.
+/// The bandits are seeded from the given random number generator, which also pulls the arms,
+/// so a seeded generator makes the whole survey reproducible.
+///
+/// - `arms` - the arms of the bandit problem.
+/// - `runs` - the number of repeated runs.
+/// - `steps` - the number of steps per run.
+/// - `rng` - the random number generator of the survey.
+///
+/// # Example
+///```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use readapt::bandits::arm::RandomArm;
+/// use readapt::bandits::experiment::survey;
+///
+/// let arms = vec![RandomArm::normal(0.0), RandomArm::normal(1.0)];
+/// let result = survey(arms, 10, 100, &mut StdRng::seed_from_u64(42));
+///
+/// for (label, rewards) in result.labels.iter().zip(result.average_reward_history) {
+///     println!("{label}: {}", rewards.iter().sum::<f64>());
+/// }
+///```
+pub fn survey<A: Arm>(
+    arms: Vec<A>,
+    runs: usize,
+    steps: usize,
+    rng: &mut dyn RngCore,
+) -> BenchmarkResult {
+    let n_arms = arms.len();
+    let mut bandits: Vec<Box<dyn Bandit>> = [
+        StochasticBandit::greedy(n_arms),
+        StochasticBandit::epsilon_greedy(n_arms, 0.1),
+        StochasticBandit::ucb(n_arms, 2.0),
+        StochasticBandit::sliding_ucb(n_arms, 2.0, 100),
+        StochasticBandit::gradient(n_arms, 0.1),
+        StochasticBandit::thompson_sampling(n_arms),
+        StochasticBandit::softmax(n_arms, 0.1),
+    ]
+    .into_iter()
+    .map(|bandit| {
+        Box::new(bandit.with_rng(StdRng::seed_from_u64(rng.next_u64()))) as Box<dyn Bandit>
+    })
+    .collect();
+    bandits.push(Box::new(
+        Exp3::new(n_arms, 0.1).with_rng(StdRng::seed_from_u64(rng.next_u64())),
+    ));
+
+    Benchmark {
+        arm: MultiArm::new(arms),
+        bandits,
+    }
+    .run_with_rng(runs, steps, rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bandits::arm::RandomArm;
+
+    #[test]
+    fn survey_all_algorithms() {
+        let arms = vec![RandomArm::normal(0.0), RandomArm::normal(1.0)];
+        let result = survey(arms.clone(), 5, 20, &mut StdRng::seed_from_u64(1));
+
+        assert_eq!(
+            result.labels,
+            vec![
+                "greedy",
+                "ε-greedy (ε=0.1)",
+                "UCB (c=2)",
+                "sliding UCB (c=2, window=100)",
+                "gradient (α=0.1)",
+                "Thompson sampling",
+                "softmax (τ=0.1)",
+                "EXP3 (γ=0.1)"
+            ]
+        );
+        assert_eq!(result.average_reward_history.len(), result.labels.len());
+        assert!(result.optimal_action_percentage_history.is_some());
+
+        // a seeded survey is reproducible
+        let again = survey(arms, 5, 20, &mut StdRng::seed_from_u64(1));
+        assert_eq!(result.average_reward_history, again.average_reward_history);
+    }
+}
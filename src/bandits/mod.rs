@@ -1,3 +1,5 @@
 pub mod arm;
 pub mod bandit;
 pub mod bench;
+pub mod contextual;
+pub mod experiment;
@@ -1,9 +1,13 @@
-use crate::mdp::model::{Action, MDPError, State, MDP};
+use crate::mdp::model::{Action, FeatureEncoder, MDPError, State, MDP};
+use crate::mdp::policy::Policy;
 use rand::distributions::WeightedIndex;
 use rand::prelude::*;
-use std::collections::HashSet;
+use rand_distr::Normal;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
 use std::fmt;
 use std::hash::Hash;
+use std::sync::Arc;
 
 /// Represents a movement action on the grid world environment.
 /// There are four possible actions, moving north or up, south or down,
@@ -24,6 +28,32 @@ impl Move {
     fn len() -> usize {
         Move::ACTIONS.len()
     }
+
+    /// Returns the movement action having the given ID, that is, the inverse of
+    /// [`Action::id`], or None if no action has the ID.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - the ID of the action
+    pub fn from_id(id: usize) -> Option<Move> {
+        match id {
+            0 => Some(Move::North),
+            1 => Some(Move::South),
+            2 => Some(Move::East),
+            3 => Some(Move::West),
+            _ => None,
+        }
+    }
+
+    /// Returns an arrow pointing towards the direction of the movement.
+    fn arrow(&self) -> char {
+        match self {
+            Move::North => '↑',
+            Move::South => '↓',
+            Move::East => '→',
+            Move::West => '←',
+        }
+    }
 }
 
 impl Action for Move {
@@ -37,14 +67,39 @@ impl Action for Move {
     }
 }
 
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self {
+            Move::North => "N",
+            Move::South => "S",
+            Move::East => "E",
+            Move::West => "W",
+        };
+        write!(f, "{direction}")
+    }
+}
+
 /// Represents a tile on the grid.
-#[derive(Debug, Eq)]
+#[derive(Debug, Clone, Eq)]
 pub struct Tile {
     id: usize,
     pub x: usize,
     pub y: usize,
 }
 
+impl Tile {
+    /// Returns the row and the column of the tile.
+    pub fn coordinates(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
 impl State for Tile {
     fn id(&self) -> usize {
         self.id
@@ -63,17 +118,153 @@ impl PartialEq for Tile {
     }
 }
 
+/// A tile in the open set of A* search, ordered such that the tile having the lowest
+/// priority is popped first from a max-heap.
+#[derive(Debug, PartialEq)]
+struct SearchNode {
+    priority: f64,
+    id: usize,
+}
+
+impl Eq for SearchNode {}
+
+impl Ord for SearchNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .total_cmp(&self.priority)
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+impl PartialOrd for SearchNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The features used to encode the tiles of a grid world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileEncoding {
+    /// A vector having one entry per tile, where only the entry of the encoded tile is one.
+    OneHot,
+    /// The (x, y) coordinates of the tile normalized to the unit interval.
+    Coordinates,
+}
+
+/// The rewards and the transition model of a grid world created from a map using
+/// [`GridWorld::from_ascii`]. The reward of a transition is the reward of the tile entered.
+pub struct AsciiConfig {
+    /// The reward for entering a goal tile.
+    pub goal_reward: f64,
+    /// The reward for entering a pit tile.
+    pub pit_reward: f64,
+    /// The reward for entering any other tile.
+    pub step_reward: f64,
+    /// A currying function that takes a movement action and returns a directional function,
+    /// as in [`GridWorld::from`].
+    pub transition_model: fn(&Move) -> fn(&Move) -> f64,
+}
+
+impl Default for AsciiConfig {
+    /// Creates a configuration having a goal reward of 1, a pit reward of -1 and a small step
+    /// penalty of -0.04, where every movement succeeds.
+    fn default() -> Self {
+        Self {
+            goal_reward: 1.0,
+            pit_reward: -1.0,
+            step_reward: -0.04,
+            transition_model: |action| match action {
+                Move::North => |d| if *d == Move::North { 1.0 } else { 0.0 },
+                Move::South => |d| if *d == Move::South { 1.0 } else { 0.0 },
+                Move::East => |d| if *d == Move::East { 1.0 } else { 0.0 },
+                Move::West => |d| if *d == Move::West { 1.0 } else { 0.0 },
+            },
+        }
+    }
+}
+
+/// A distribution of transition rewards, used by [`GridWorld::with_reward_distributions`].
+/// In contrast to [`Distribution`], it can be boxed, and it exposes its mean, which is the
+/// expected reward that model-based optimizers plan with.
+pub trait RewardDistribution {
+    /// Samples a reward.
+    fn sample(&self, rng: &mut dyn RngCore) -> f64;
+    /// Returns the mean reward.
+    fn mean(&self) -> f64;
+}
+
+/// A fixed reward is a degenerate distribution.
+impl RewardDistribution for f64 {
+    fn sample(&self, _: &mut dyn RngCore) -> f64 {
+        *self
+    }
+
+    fn mean(&self) -> f64 {
+        *self
+    }
+}
+
+impl RewardDistribution for Normal<f64> {
+    fn sample(&self, rng: &mut dyn RngCore) -> f64 {
+        Distribution::sample(self, rng)
+    }
+
+    fn mean(&self) -> f64 {
+        Normal::mean(self)
+    }
+}
+
+/// The reward distributions of a grid world indexed by state ID, action ID and next state ID.
+pub type RewardDistributions = Vec<Vec<Vec<Box<dyn RewardDistribution + Send + Sync>>>>;
+
 /// Represents a grid-based Markov Decision Process (MDP).
 ///
 /// `GridWorld` is commonly used in reinforcement learning to model an agent navigating
 /// a 2-dimensional grid of stochastic transitions, collecting rewards.
+///
+/// Two grid worlds are equal if they have the same dimensions, terminal states and discount
+/// factor, and their transition and reward matrices agree within [`GridWorld::TOLERANCE`].
+/// The tile encoding and the start tile are not taken into account.
+#[derive(Clone)]
 pub struct GridWorld {
     rows: usize,
     columns: usize,
     states: Vec<Tile>,
     transition_probabilities: Vec<Vec<Vec<f64>>>,
     rewards: Vec<Vec<Vec<f64>>>,
+    // the next tile ID, probability and reward of the possible transitions of each tile and move
+    transitions: Vec<Vec<Vec<(usize, f64, f64)>>>,
     terminal_states: HashSet<usize>,
+    walls: HashSet<usize>,
+    encoding: TileEncoding,
+    discount_factor: f64,
+    start: Option<usize>,
+    // the reward distribution of each triplet (s, a, s'), if the rewards are stochastic
+    reward_distributions: Option<Arc<RewardDistributions>>,
+}
+
+impl PartialEq for GridWorld {
+    fn eq(&self, other: &Self) -> bool {
+        let approx_eq = |a: &Vec<Vec<Vec<f64>>>, b: &Vec<Vec<Vec<f64>>>| {
+            a.iter().flatten().flatten().count() == b.iter().flatten().flatten().count()
+                && a.iter()
+                    .flatten()
+                    .flatten()
+                    .zip(b.iter().flatten().flatten())
+                    .all(|(x, y)| (x - y).abs() <= GridWorld::TOLERANCE)
+        };
+
+        self.rows == other.rows
+            && self.columns == other.columns
+            && self.terminal_states == other.terminal_states
+            && approx_eq(
+                &self.transition_probabilities,
+                &other.transition_probabilities,
+            )
+            && approx_eq(&self.rewards, &other.rewards)
+            && self.discount_factor == other.discount_factor
+    }
 }
 
 impl fmt::Display for GridWorld {
@@ -153,13 +344,16 @@ impl fmt::Display for GridWorld {
 }
 
 impl GridWorld {
+    /// Tolerance used when comparing probabilities and rewards of grid worlds.
+    pub const TOLERANCE: f64 = 1e-9;
+
     /// Creates a custom Grid World.
     ///
     /// # Notes
     ///
     /// 1. The grid cannot be empty.
     /// 2. The transition and reward matrices must have dimensions SxAxS, where S is the number of states and A the number of actions.
-    /// 3. The transition probabilities for each action must sum to 1.
+    /// 3. The transition probabilities for each action must sum to 1, within [`GridWorld::TOLERANCE`].
     ///
     /// # Arguments
     ///
@@ -183,21 +377,23 @@ impl GridWorld {
             return Err(MDPError::Empty);
         }
         if transition_probabilities.len() != n_states
-            || transition_probabilities[0].len() != Move::len()
-            || transition_probabilities[0][0].len() != n_states
+            || transition_probabilities
+                .iter()
+                .any(|t| t.len() != Move::len() || t.iter().any(|a| a.len() != n_states))
         {
             return Err(MDPError::InvalidTransitionMatrix);
         }
-        for t in transition_probabilities.iter() {
-            for a in t.iter() {
-                if a.iter().sum::<f64>() != 1f64 {
-                    return Err(MDPError::InvalidTransitionMatrix);
-                }
-            }
+        if transition_probabilities
+            .iter()
+            .flatten()
+            .any(|probabilities| !Self::is_distribution(probabilities))
+        {
+            return Err(MDPError::InvalidTransitionMatrix);
         }
         if rewards.len() != n_states
-            || rewards[0].len() != Move::len()
-            || rewards[0][0].len() != n_states
+            || rewards
+                .iter()
+                .any(|r| r.len() != Move::len() || r.iter().any(|a| a.len() != n_states))
         {
             return Err(MDPError::InvalidRewardMatrix);
         }
@@ -222,9 +418,57 @@ impl GridWorld {
             rows,
             columns,
             states,
+            transitions: Self::sparse_transitions(&transition_probabilities, &rewards),
             transition_probabilities,
             rewards,
             terminal_states,
+            walls: HashSet::new(),
+            encoding: TileEncoding::OneHot,
+            discount_factor: 1.0,
+            start: None,
+            reward_distributions: None,
+        })
+    }
+
+    /// Creates a custom Grid World exactly as [`GridWorld::new`], but having stochastic rewards.
+    /// Each triplet (s, a, s') has a reward distribution, and [`MDP::reward`] returns a sample
+    /// of it, while [`MDP::transitions`] yields its mean. Thus, model-based optimizers plan
+    /// with the expected rewards, while episodes and model-free optimizers observe noisy ones.
+    ///
+    /// # Arguments
+    ///
+    /// - `rows` - number of rows
+    /// - `columns` - number of columns
+    /// - `transition_probabilities` - a matrix of dimension SxAxS holding the movement probabilities for each triplet (s, a, s')
+    /// - `reward_distributions` - a matrix of dimension SxAxS holding the reward distribution of each triplet (s, a, s')
+    /// - `is_terminal_state` - a function returning true when any given tile state is terminal
+    pub fn with_reward_distributions<'a>(
+        rows: usize,
+        columns: usize,
+        transition_probabilities: Vec<Vec<Vec<f64>>>,
+        reward_distributions: RewardDistributions,
+        is_terminal_state: fn(&Tile) -> bool,
+    ) -> Result<Self, MDPError<'a, Tile>> {
+        let rewards = reward_distributions
+            .iter()
+            .map(|actions| {
+                actions
+                    .iter()
+                    .map(|next_states| next_states.iter().map(|d| d.mean()).collect())
+                    .collect()
+            })
+            .collect();
+        let grid = Self::new(
+            rows,
+            columns,
+            transition_probabilities,
+            rewards,
+            is_terminal_state,
+        )?;
+
+        Ok(Self {
+            reward_distributions: Some(Arc::new(reward_distributions)),
+            ..grid
         })
     }
 
@@ -252,6 +496,98 @@ impl GridWorld {
         transition_model: fn(&Move) -> fn(&Move) -> f64,
         reward: fn(&Tile) -> f64,
         is_terminal_state: fn(&Tile) -> bool,
+    ) -> Result<Self, MDPError<'a, Tile>> {
+        Self::from_model(
+            rows,
+            columns,
+            is_wall,
+            transition_model,
+            reward,
+            is_terminal_state,
+        )
+    }
+
+    /// Creates a Grid World from a map, where each line is a row of tiles and each character
+    /// is a tile, that is, `S` for the start, `G` for a goal, `X` for a pit, `#` for a wall and
+    /// `.` for an empty tile. Goals and pits are terminal tiles, while walls are impassable.
+    /// The number of rows and columns is inferred from the map, ignoring leading and trailing
+    /// whitespace and empty lines.
+    ///
+    /// # Arguments
+    ///
+    /// - `map` - the map of the grid
+    /// - `config` - the rewards and the transition model of the grid
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use readapt::mdp::environment::{AsciiConfig, GridWorld};
+    /// use readapt::mdp::model::MDP;
+    ///
+    /// let grid = GridWorld::from_ascii(
+    ///     "
+    ///     ...G
+    ///     .#.X
+    ///     S...
+    ///     ",
+    ///     AsciiConfig::default(),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(grid.n_states(), 12);
+    /// assert_eq!(grid.start_state().map(|tile| (tile.x, tile.y)), Some((2, 0)));
+    /// ```
+    pub fn from_ascii<'a>(map: &str, config: AsciiConfig) -> Result<Self, MDPError<'a, Tile>> {
+        let lines: Vec<Vec<char>> = map
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().collect())
+            .collect();
+
+        let rows = lines.len();
+        let columns = lines.first().map(|line| line.len()).unwrap_or(0);
+        if rows == 0 || columns == 0 {
+            return Err(MDPError::Empty);
+        }
+        if lines.iter().any(|line| line.len() != columns)
+            || lines
+                .iter()
+                .flatten()
+                .any(|c| !matches!(c, 'S' | 'G' | 'X' | '#' | '.'))
+            || lines.iter().flatten().filter(|&&c| c == 'S').count() > 1
+        {
+            return Err(MDPError::InvalidMap);
+        }
+
+        let tile = |state: &Tile| lines[state.x][state.y];
+        let grid = Self::from_model(
+            rows,
+            columns,
+            |state| tile(state) == '#',
+            config.transition_model,
+            |state| match tile(state) {
+                'G' => config.goal_reward,
+                'X' => config.pit_reward,
+                _ => config.step_reward,
+            },
+            |state| matches!(tile(state), 'G' | 'X'),
+        )?;
+
+        Ok(Self {
+            start: lines.iter().flatten().position(|&c| c == 'S'),
+            ..grid
+        })
+    }
+
+    /// Creates a Grid World exactly as [`GridWorld::from`], but accepts closures.
+    fn from_model<'a>(
+        rows: usize,
+        columns: usize,
+        is_wall: impl Fn(&Tile) -> bool,
+        transition_model: fn(&Move) -> fn(&Move) -> f64,
+        reward: impl Fn(&Tile) -> f64,
+        is_terminal_state: impl Fn(&Tile) -> bool,
     ) -> Result<Self, MDPError<'a, Tile>> {
         // Check if the grid is empty
         if rows == 0 || columns == 0 {
@@ -261,6 +597,7 @@ impl GridWorld {
         let n_states = rows * columns;
         let mut states = Vec::with_capacity(n_states);
         let mut terminal_states = HashSet::new();
+        let mut walls = HashSet::new();
 
         // Create the states
         for r in 0..rows {
@@ -274,6 +611,9 @@ impl GridWorld {
                 if is_terminal_state(&state) {
                     terminal_states.insert(state.id());
                 }
+                if is_wall(&state) {
+                    walls.insert(state.id());
+                }
 
                 states.push(state);
             }
@@ -364,25 +704,440 @@ impl GridWorld {
             }
         }
 
-        // Check if the transition probabilities sum to 1 for each action
-        for t in transition_probabilities.iter() {
-            for a in t.iter() {
-                if a.iter().sum::<f64>() != 1f64 {
-                    return Err(MDPError::InvalidTransitionMatrix);
-                }
-            }
+        // Check if the transition probabilities form a distribution for each action
+        if transition_probabilities
+            .iter()
+            .flatten()
+            .any(|probabilities| !Self::is_distribution(probabilities))
+        {
+            return Err(MDPError::InvalidTransitionMatrix);
         }
 
         Ok(Self {
             rows,
             columns,
             states,
+            transitions: Self::sparse_transitions(&transition_probabilities, &rewards),
             transition_probabilities,
             rewards,
             terminal_states,
+            walls,
+            encoding: TileEncoding::OneHot,
+            discount_factor: 1.0,
+            start: None,
+            reward_distributions: None,
+        })
+    }
+
+    /// Renders the grid using the same box layout as `Display`, where the content of each
+    /// cell spans two lines produced by the given function.
+    fn render<F: Fn(&Tile) -> (String, String)>(&self, cell: F) -> String {
+        let border = format!("{}+\n", "+-------------".repeat(self.columns));
+        let mut output = String::new();
+
+        for row in 0..self.rows {
+            output.push_str(&border);
+            let lines: Vec<(String, String)> = (0..self.columns)
+                .map(|col| cell(&self.states[row * self.columns + col]))
+                .collect();
+
+            for line in [
+                lines.iter().map(|(first, _)| first).collect::<Vec<_>>(),
+                lines.iter().map(|(_, second)| second).collect::<Vec<_>>(),
+            ] {
+                for content in line {
+                    output.push_str(&format!("|{content:^13}"));
+                }
+                output.push_str("|\n");
+            }
+        }
+
+        output.push_str(&border);
+        output
+    }
+
+    /// Renders a policy over the grid, that is, the action of the policy as an arrow. Terminal
+    /// tiles are marked by `T` and walls are filled with `#`, while tiles having no policy
+    /// action are marked by `?`.
+    ///
+    /// # Arguments
+    ///
+    /// - `policy` - the policy to render
+    pub fn render_policy(&self, policy: &Policy<Tile, Move>) -> String {
+        self.render(|state| {
+            if self.walls.contains(&state.id) {
+                ("#".repeat(13), "#".repeat(13))
+            } else if self.is_terminal(state) {
+                (String::from("T"), String::new())
+            } else {
+                let arrow = policy
+                    .select_action(state)
+                    .map(|action| action.arrow())
+                    .unwrap_or('?');
+                (arrow.to_string(), String::new())
+            }
+        })
+    }
+
+    /// Renders a solution of the grid, that is, the action of the policy as an arrow along
+    /// with the value of each tile. Terminal tiles are marked by `=T=` and walls are filled
+    /// with `#`, while tiles having no policy action are marked by `?`.
+    ///
+    /// # Arguments
+    ///
+    /// - `policy` - the policy to render
+    /// - `values` - the state values indexed by state ID
+    pub fn render_solution(&self, policy: &Policy<Tile, Move>, values: &[f64]) -> String {
+        self.render(|state| {
+            if self.walls.contains(&state.id) {
+                ("#".repeat(13), "#".repeat(13))
+            } else if self.is_terminal(state) {
+                (String::from("=T="), format!("{:.2}", values[state.id]))
+            } else {
+                let arrow = policy
+                    .select_action(state)
+                    .map(|action| action.arrow())
+                    .unwrap_or('?');
+                (arrow.to_string(), format!("{:.2}", values[state.id]))
+            }
         })
     }
 
+    /// Returns the smallest horizon `H` such that the policy reaches a terminal tile within
+    /// `H` steps from the starting tile with probability at least `p`, or none if this does
+    /// not happen within the maximum number of steps. The probability of reaching a terminal
+    /// is computed exactly by propagating the distribution over tiles induced by the policy.
+    /// The horizon is a sensible choice for the maximum number of steps of `run_policy`.
+    ///
+    /// Note that tiles having no policy action retain their probability mass, which thus
+    /// never reaches a terminal.
+    ///
+    /// # Arguments
+    ///
+    /// - `policy` - the policy to be executed
+    /// - `starting_state` - the tile the agent starts from
+    /// - `p` - the required probability of reaching a terminal, between 0 and 1
+    /// - `maximum_steps` - the maximum horizon
+    pub fn horizon_for_confidence(
+        &self,
+        policy: &Policy<Tile, Move>,
+        starting_state: &Tile,
+        p: f64,
+        maximum_steps: usize,
+    ) -> Option<usize> {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("Invalid probability value: {p}");
+        }
+
+        let mut distribution = vec![0.0; self.n_states()];
+        distribution[starting_state.id] = 1.0;
+
+        for step in 0..=maximum_steps {
+            // the terminal tiles are absorbing, so their mass is the probability of reaching them
+            let absorbed: f64 = self
+                .terminal_states
+                .iter()
+                .map(|&id| distribution[id])
+                .sum();
+            if absorbed >= p {
+                return Some(step);
+            }
+
+            let mut next_distribution = vec![0.0; self.n_states()];
+            for state in self.states.iter() {
+                let mass = distribution[state.id];
+                match policy.select_action(state) {
+                    Some(action) if mass > 0.0 && !self.is_terminal(state) => {
+                        for (next_state_id, probability) in self.transition_probabilities[state.id]
+                            [action.id()]
+                        .iter()
+                        .enumerate()
+                        {
+                            next_distribution[next_state_id] += mass * probability;
+                        }
+                    }
+                    _ => next_distribution[state.id] += mass,
+                }
+            }
+            distribution = next_distribution;
+        }
+
+        None
+    }
+
+    /// Returns the Manhattan distance between two tiles, that is, the minimum number of moves
+    /// between them on a grid without walls. It is an admissible heuristic for A* search.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - the first tile
+    /// - `to` - the second tile
+    pub fn manhattan_distance(from: &Tile, to: &Tile) -> f64 {
+        (from.x.abs_diff(to.x) + from.y.abs_diff(to.y)) as f64
+    }
+
+    /// Returns a shortest path between two tiles using A* search guided by the given
+    /// heuristic, or none if the goal is unreachable. Every move costs one step and a move is
+    /// possible if any action leads to the neighboring tile with positive probability. The
+    /// path is optimal if the heuristic never overestimates the remaining number of moves,
+    /// e.g., [`GridWorld::manhattan_distance`].
+    ///
+    /// # Arguments
+    ///
+    /// - `start` - the tile the path starts from
+    /// - `goal` - the tile the path ends at
+    /// - `heuristic` - an estimate of the number of moves from a tile to the goal
+    pub fn shortest_path(
+        &self,
+        start: &Tile,
+        goal: &Tile,
+        heuristic: impl Fn(&Tile, &Tile) -> f64,
+    ) -> Option<Vec<&Tile>> {
+        self.weighted_a_star(start, goal, heuristic, 1.0)
+    }
+
+    /// Returns a path between two tiles using weighted A* search, which inflates the
+    /// heuristic by the given weight, or none if the goal is unreachable. Larger weights
+    /// expand fewer tiles, but the path may be up to `weight` times longer than the shortest
+    /// one. A weight of one is plain A* search.
+    ///
+    /// # Arguments
+    ///
+    /// - `start` - the tile the path starts from
+    /// - `goal` - the tile the path ends at
+    /// - `heuristic` - an estimate of the number of moves from a tile to the goal
+    /// - `weight` - the inflation factor of the heuristic, at least one
+    pub fn weighted_a_star(
+        &self,
+        start: &Tile,
+        goal: &Tile,
+        heuristic: impl Fn(&Tile, &Tile) -> f64,
+        weight: f64,
+    ) -> Option<Vec<&Tile>> {
+        if weight < 1.0 {
+            panic!("Invalid weight value: {weight}");
+        }
+
+        let mut costs = vec![f64::INFINITY; self.n_states()];
+        let mut parents: Vec<Option<usize>> = vec![None; self.n_states()];
+        let mut open = BinaryHeap::new();
+
+        costs[start.id] = 0.0;
+        open.push(SearchNode {
+            priority: weight * heuristic(start, goal),
+            id: start.id,
+        });
+
+        while let Some(SearchNode { id, .. }) = open.pop() {
+            if id == goal.id {
+                // reconstruct the path backwards from the goal
+                let mut path = vec![&self.states[id]];
+                while let Some(parent) = parents[path.last().unwrap().id] {
+                    path.push(&self.states[parent]);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            // only successors having positive probability are stored in the sparse transitions
+            for &(next_id, _, _) in self.transitions[id].iter().flatten() {
+                if next_id != id && costs[id] + 1.0 < costs[next_id] {
+                    costs[next_id] = costs[id] + 1.0;
+                    parents[next_id] = Some(id);
+                    open.push(SearchNode {
+                        priority: costs[next_id] + weight * heuristic(&self.states[next_id], goal),
+                        id: next_id,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Creates a Grid World exactly as [`GridWorld::from`], but additionally checks that the
+    /// configuration of walls and terminal states is sensible, that is, at least one terminal
+    /// state is reachable from every tile that is not a wall. Misconfigured grids, e.g., a
+    /// terminal state enclosed by walls, are thus caught at construction rather than after a
+    /// confusing solve.
+    ///
+    /// # Arguments
+    ///
+    /// - `rows` - number of rows
+    /// - `columns` - number of columns
+    /// - `is_wall` - a function checking if any given tile is a wall
+    /// - `transition_model` - a currying function that takes a movement action and returns a directional function
+    /// - `reward` - a function assigning a reward to any given tile state
+    /// - `is_terminal_state` - a function checking if any given tile state is terminal
+    pub fn from_checked<'a>(
+        rows: usize,
+        columns: usize,
+        is_wall: fn(&Tile) -> bool,
+        transition_model: fn(&Move) -> fn(&Move) -> f64,
+        reward: fn(&Tile) -> f64,
+        is_terminal_state: fn(&Tile) -> bool,
+    ) -> Result<Self, MDPError<'a, Tile>> {
+        let grid = Self::from(
+            rows,
+            columns,
+            is_wall,
+            transition_model,
+            reward,
+            is_terminal_state,
+        )?;
+
+        // Search backwards from the terminal states for every tile that can reach them
+        let mut reachable = vec![false; grid.n_states()];
+        let mut queue: VecDeque<usize> = grid.terminal_states.iter().copied().collect();
+        for &id in queue.iter() {
+            reachable[id] = true;
+        }
+
+        while let Some(next_state_id) = queue.pop_front() {
+            for state in grid.states.iter() {
+                if reachable[state.id] || is_wall(state) {
+                    continue;
+                }
+
+                if Move::ACTIONS.iter().any(|action| {
+                    grid.transition_probabilities[state.id][action.id()][next_state_id] > 0.0
+                }) {
+                    reachable[state.id] = true;
+                    queue.push_back(state.id);
+                }
+            }
+        }
+
+        match grid
+            .states
+            .iter()
+            .find(|state| !reachable[state.id] && !is_wall(state))
+        {
+            Some(state) => Err(MDPError::UnreachableTerminal { id: state.id }),
+            None => Ok(grid),
+        }
+    }
+
+    /// Returns true if the given probabilities form a distribution, that is, they all lie in
+    /// `[0, 1]` and sum to 1 within [`GridWorld::TOLERANCE`]. Note that this also rejects
+    /// rows containing NaN, which could not be sampled when acting.
+    fn is_distribution(probabilities: &[f64]) -> bool {
+        probabilities.iter().all(|p| (0.0..=1.0).contains(p))
+            && (probabilities.iter().sum::<f64>() - 1.0).abs() <= Self::TOLERANCE
+    }
+
+    /// Returns the possible transitions of each tile and move, that is, the next tile ID, the
+    /// probability and the reward of every transition having a positive probability.
+    fn sparse_transitions(
+        transition_probabilities: &[Vec<Vec<f64>>],
+        rewards: &[Vec<Vec<f64>>],
+    ) -> Vec<Vec<Vec<(usize, f64, f64)>>> {
+        transition_probabilities
+            .iter()
+            .zip(rewards)
+            .map(|(moves, move_rewards)| {
+                moves
+                    .iter()
+                    .zip(move_rewards)
+                    .map(|(probabilities, rewards)| {
+                        probabilities
+                            .iter()
+                            .zip(rewards)
+                            .enumerate()
+                            .filter(|(_, (&p, _))| p > 0.0)
+                            .map(|(id, (&p, &r))| (id, p, r))
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the ID of the tile at the given row and column.
+    ///
+    /// # Arguments
+    ///
+    /// - `row` - the row of the tile, which must be within the grid
+    /// - `col` - the column of the tile, which must be within the grid
+    pub fn index_of(&self, row: usize, col: usize) -> usize {
+        if row >= self.rows {
+            panic!("Invalid row value: {row}");
+        }
+        if col >= self.columns {
+            panic!("Invalid column value: {col}");
+        }
+
+        row * self.columns + col
+    }
+
+    /// Returns the tile at the given row and column, or None if it is outside the grid.
+    ///
+    /// # Arguments
+    ///
+    /// - `row` - the row of the tile
+    /// - `col` - the column of the tile
+    pub fn state_at(&self, row: usize, col: usize) -> Option<&Tile> {
+        if row < self.rows && col < self.columns {
+            Some(&self.states[self.index_of(row, col)])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the start tile of a grid created from a map, if the map has one, or of a windy
+    /// grid.
+    pub fn start_state(&self) -> Option<&Tile> {
+        self.start.map(|id| &self.states[id])
+    }
+
+    /// Sets the features used to encode the tiles of the grid. By default, tiles are one-hot
+    /// encoded.
+    ///
+    /// # Arguments
+    ///
+    /// - `encoding` - the tile encoding
+    pub fn with_encoding(self, encoding: TileEncoding) -> Self {
+        Self { encoding, ..self }
+    }
+
+    /// Sets the discount factor of the grid. By default, the grid is undiscounted.
+    ///
+    /// # Arguments
+    ///
+    /// - `gamma` - the discount factor, between 0 and 1.
+    pub fn with_discount_factor(self, gamma: f64) -> Self {
+        if !(0.0..=1.0).contains(&gamma) {
+            panic!("Invalid gamma value: {gamma}");
+        }
+
+        Self {
+            discount_factor: gamma,
+            ..self
+        }
+    }
+
+    /// Validates that the rewards do not form a degenerate structure that breaks optimality.
+    /// In particular, when the grid is undiscounted, a non-terminal tile having a positive
+    /// reward for remaining in place yields an infinite value, which manifests as value
+    /// iteration never converging.
+    pub fn validate_rewards(&self) -> Result<(), MDPError<'_, Tile>> {
+        if self.discount_factor() < 1.0 {
+            return Ok(());
+        }
+
+        for state in self.states.iter().filter(|state| !self.is_terminal(state)) {
+            for action in Move::ACTIONS.iter() {
+                if self.transition_probabilities[state.id][action.id()][state.id] > 0.0
+                    && self.rewards[state.id][action.id()][state.id] > 0.0
+                {
+                    return Err(MDPError::PositiveRewardCycle { state });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// In the corner problem the upper-left corner and the bottom-right corner
     /// are self-absorbing terminal states. Each transition that is not terminal
     /// results in a reward penalty of -1. Agent movement success is user-defined.
@@ -475,22 +1230,155 @@ impl GridWorld {
             }
         }
 
-        // Check if the transition probabilities sum to 1 for each action
-        for t in transition_probabilities.iter() {
-            for a in t.iter() {
-                if a.iter().sum::<f64>() != 1f64 {
-                    return Err(MDPError::InvalidTransitionMatrix);
+        // Check if the transition probabilities form a distribution for each action
+        if transition_probabilities
+            .iter()
+            .flatten()
+            .any(|probabilities| !Self::is_distribution(probabilities))
+        {
+            return Err(MDPError::InvalidTransitionMatrix);
+        }
+
+        Ok(Self {
+            rows,
+            columns,
+            states,
+            transitions: Self::sparse_transitions(&transition_probabilities, &rewards),
+            transition_probabilities,
+            rewards,
+            terminal_states,
+            walls: HashSet::new(),
+            encoding: TileEncoding::OneHot,
+            discount_factor: 1.0,
+            start: None,
+            reward_distributions: None,
+        })
+    }
+
+    /// In the windy grid world of Sutton and Barto, a wind blows upward through each column,
+    /// shifting the agent north by the wind strength of the column it moves from, in addition
+    /// to its own movement. Moves are deterministic and movement off the grid is clipped.
+    /// Every step results in a reward penalty of -1 until the goal, which is a self-absorbing
+    /// terminal state, is reached. The agent starts in the middle row of the first column,
+    /// while the goal lies in the middle row at seven tenths of the columns, as in the book.
+    ///
+    /// # Arguments
+    ///
+    /// - `rows` - number of rows
+    /// - `columns` - number of columns
+    /// - `wind` - the upward wind strength of each column
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use readapt::mdp::environment::GridWorld;
+    ///
+    /// let grid = GridWorld::windy(7, 10, vec![0, 0, 0, 1, 1, 1, 2, 2, 1, 0]).unwrap();
+    /// assert_eq!(grid.start_state().map(|tile| (tile.x, tile.y)), Some((3, 0)));
+    /// ```
+    pub fn windy<'a>(
+        rows: usize,
+        columns: usize,
+        wind: Vec<usize>,
+    ) -> Result<Self, MDPError<'a, Tile>> {
+        Self::windy_with_endpoints(
+            rows,
+            columns,
+            wind,
+            (rows / 2, 0),
+            (rows / 2, columns * 7 / 10),
+        )
+    }
+
+    /// Creates a windy grid world exactly as [`GridWorld::windy`], but having the given start
+    /// and goal tiles.
+    ///
+    /// # Arguments
+    ///
+    /// - `rows` - number of rows
+    /// - `columns` - number of columns
+    /// - `wind` - the upward wind strength of each column
+    /// - `start` - the row and column of the start tile, which must be within the grid
+    /// - `goal` - the row and column of the goal tile, which must be within the grid
+    pub fn windy_with_endpoints<'a>(
+        rows: usize,
+        columns: usize,
+        wind: Vec<usize>,
+        start: (usize, usize),
+        goal: (usize, usize),
+    ) -> Result<Self, MDPError<'a, Tile>> {
+        if rows == 0 || columns == 0 {
+            return Err(MDPError::Empty);
+        }
+        if wind.len() != columns
+            || start.0 >= rows
+            || start.1 >= columns
+            || goal.0 >= rows
+            || goal.1 >= columns
+        {
+            return Err(MDPError::InvalidMap);
+        }
+
+        let n_states = rows * columns;
+        let goal_id = goal.0 * columns + goal.1;
+        let mut states = Vec::with_capacity(n_states);
+        let mut transition_probabilities = vec![vec![vec![0.0; n_states]; Move::len()]; n_states];
+        let mut rewards = vec![vec![vec![0.0; n_states]; Move::len()]; n_states];
+
+        for r in 0..rows {
+            for (c, &strength) in wind.iter().enumerate() {
+                let state = Tile {
+                    id: r * columns + c,
+                    x: r,
+                    y: c,
+                };
+
+                for action in Move::ACTIONS.iter() {
+                    if state.id == goal_id {
+                        // Self absorbing goal (all actions result to the same state)
+                        transition_probabilities[state.id][action.id()][state.id] = 1f64;
+                        continue;
+                    }
+
+                    let (row, col) = match action {
+                        Move::North => (r.saturating_sub(1), c),
+                        Move::South => ((r + 1).min(rows - 1), c),
+                        Move::East => (r, (c + 1).min(columns - 1)),
+                        Move::West => (r, c.saturating_sub(1)),
+                    };
+                    // the wind of the column the agent moves from pushes it north
+                    let next_state_id = row.saturating_sub(strength) * columns + col;
+
+                    transition_probabilities[state.id][action.id()][next_state_id] = 1f64;
+                    rewards[state.id][action.id()][next_state_id] = -1f64;
                 }
+
+                states.push(state);
             }
         }
 
+        // Check if the transition probabilities form a distribution for each action
+        if transition_probabilities
+            .iter()
+            .flatten()
+            .any(|probabilities| !Self::is_distribution(probabilities))
+        {
+            return Err(MDPError::InvalidTransitionMatrix);
+        }
+
         Ok(Self {
             rows,
             columns,
             states,
+            transitions: Self::sparse_transitions(&transition_probabilities, &rewards),
             transition_probabilities,
             rewards,
-            terminal_states,
+            terminal_states: HashSet::from([goal_id]),
+            walls: HashSet::new(),
+            encoding: TileEncoding::OneHot,
+            discount_factor: 1.0,
+            start: Some(start.0 * columns + start.1),
+            reward_distributions: None,
         })
     }
 }
@@ -516,30 +1404,114 @@ impl MDP<Tile, Move> for GridWorld {
         self.terminal_states.contains(&state.id())
     }
 
+    /// The start tile of a grid created from a map having one, or else every tile.
+    fn start_states(&self) -> &[Tile] {
+        match self.start {
+            Some(id) => std::slice::from_ref(&self.states[id]),
+            None => &self.states,
+        }
+    }
+
+    fn discount_factor(&self) -> f64 {
+        self.discount_factor
+    }
+
+    /// An action is unavailable on a non-terminal tile if it certainly leaves the agent in
+    /// place, that is, every possible outcome of the move bumps into a wall or the grid edge.
+    fn is_action_available(&self, state: &Tile, action: &Move) -> bool {
+        self.is_terminal(state)
+            || self.transition_probabilities[state.id()][action.id()][state.id()] < 1.0
+    }
+
     fn transition_probability(&self, state: &Tile, action: &Move, next_state: &Tile) -> f64 {
         self.transition_probabilities[state.id()][action.id()][next_state.id()]
     }
 
+    /// Returns the reward of the triplet, which is sampled from its distribution if the grid
+    /// has stochastic rewards.
     fn reward(&self, state: &Tile, action: &Move, next_state: &Tile) -> f64 {
-        self.rewards[state.id()][action.id()][next_state.id()]
+        match &self.reward_distributions {
+            Some(distributions) => distributions[state.id()][action.id()][next_state.id()]
+                .sample(&mut rand::thread_rng()),
+            None => self.rewards[state.id()][action.id()][next_state.id()],
+        }
+    }
+
+    fn transitions<'a>(
+        &'a self,
+        state: &'a Tile,
+        action: &'a Move,
+    ) -> impl Iterator<Item = (&'a Tile, f64, f64)> + 'a {
+        self.transitions[state.id()][action.id()]
+            .iter()
+            .map(|&(id, p, r)| (&self.states[id], p, r))
     }
 
-    fn act(&self, state: &Tile, action: &Move) -> &Tile {
+    fn transition_table(&self, state: &Tile, action: &Move) -> Vec<(usize, f64, f64)> {
+        self.transitions[state.id()][action.id()].clone()
+    }
+
+    /// Samples the next tile according to the transition probabilities. If the probabilities
+    /// of the given state and action are degenerate, e.g., all zero or not finite, the agent
+    /// deterministically moves to the highest-id successor having a positive and finite
+    /// probability, or else remains in the same state.
+    fn act_with<R: Rng + ?Sized>(&self, state: &Tile, action: &Move, rng: &mut R) -> &Tile {
         let probs = &self.transition_probabilities[state.id()][action.id()];
-        let next_state_id = WeightedIndex::new(probs)
-            .unwrap()
-            .sample(&mut rand::thread_rng());
+        match WeightedIndex::new(probs) {
+            Ok(distribution) => &self.states()[distribution.sample(rng)],
+            Err(_) => probs
+                .iter()
+                .rposition(|&p| p > 0.0 && p.is_finite())
+                .map_or(&self.states()[state.id()], |id| &self.states()[id]),
+        }
+    }
+}
 
-        &self.states()[next_state_id]
+impl FeatureEncoder<Tile> for GridWorld {
+    fn n_features(&self) -> usize {
+        match self.encoding {
+            TileEncoding::OneHot => self.n_states(),
+            TileEncoding::Coordinates => 2,
+        }
+    }
+
+    fn features(&self, state: &Tile) -> Vec<f64> {
+        match self.encoding {
+            TileEncoding::OneHot => {
+                let mut features = vec![0.0; self.n_states()];
+                features[state.id] = 1.0;
+                features
+            }
+            TileEncoding::Coordinates => {
+                // a single row or column is mapped to zero
+                let normalize = |value: usize, size: usize| {
+                    if size > 1 {
+                        value as f64 / (size - 1) as f64
+                    } else {
+                        0.0
+                    }
+                };
+                vec![
+                    normalize(state.x, self.rows),
+                    normalize(state.y, self.columns),
+                ]
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::mdp::{
-        environment::{GridWorld, Move},
-        model::MDPError,
+        environment::{
+            AsciiConfig, GridWorld, Move, RewardDistribution, RewardDistributions, TileEncoding,
+        },
+        model::{Action, FeatureEncoder, MDPError, MDP},
+        optimizer::ValueIteration,
+        policy::Policy,
     };
+    use rand_distr::Normal;
+    use std::collections::HashMap;
 
     #[test]
     fn empty_grid() {
@@ -548,6 +1520,107 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rounded_probabilities() {
+        // moving uniformly to any tile, where ninths do not sum exactly to 1
+        let transitions = vec![vec![vec![1.0 / 9.0; 9]; 4]; 9];
+        let rewards = vec![vec![vec![0f64; 9]; 4]; 9];
+        assert_ne!(transitions[0][0].iter().sum::<f64>(), 1.0);
+        assert!(GridWorld::new(3, 3, transitions, rewards, |_| false).is_ok());
+
+        // deviations beyond the tolerance are still rejected
+        let transitions = vec![vec![vec![0.1; 9]; 4]; 9];
+        let rewards = vec![vec![vec![0f64; 9]; 4]; 9];
+        assert!(matches!(
+            GridWorld::new(3, 3, transitions, rewards, |_| false),
+            Err(MDPError::InvalidTransitionMatrix)
+        ));
+    }
+
+    #[test]
+    fn degenerate_probabilities() {
+        let rewards = vec![vec![vec![0f64; 4]; 4]; 4];
+        let valid = vec![vec![vec![0.25; 4]; 4]; 4];
+        assert!(GridWorld::new(2, 2, valid.clone(), rewards.clone(), |_| false).is_ok());
+
+        // NaN, negative probabilities summing to 1 and ragged rows are rejected
+        let mut nan = valid.clone();
+        nan[1][2] = vec![f64::NAN, 0.5, 0.5, 0.0];
+        let mut negative = valid.clone();
+        negative[3][0] = vec![1.5, -0.5, 0.0, 0.0];
+        let mut ragged = valid.clone();
+        ragged[2][1] = vec![1.0];
+        for transitions in [nan, negative, ragged] {
+            assert!(matches!(
+                GridWorld::new(2, 2, transitions, rewards.clone(), |_| false),
+                Err(MDPError::InvalidTransitionMatrix)
+            ));
+        }
+
+        // ragged reward rows are rejected as well
+        let mut ragged = rewards.clone();
+        ragged[3][2] = vec![0.0; 3];
+        assert!(matches!(
+            GridWorld::new(2, 2, valid, ragged, |_| false),
+            Err(MDPError::InvalidRewardMatrix)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid gamma value: 1.5")]
+    fn invalid_discount_factor() {
+        let _ = GridWorld::corner(3, 3, 0.8)
+            .unwrap()
+            .with_discount_factor(1.5);
+    }
+
+    #[test]
+    fn ascii_map() {
+        let grid = GridWorld::from_ascii(
+            "
+            S.#
+            .XG
+            ",
+            AsciiConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(grid.n_states(), 6);
+        assert_eq!(grid.start_state(), Some(&grid.states()[0]));
+        assert!(grid.is_terminal(&grid.states()[4]));
+        assert!(grid.is_terminal(&grid.states()[5]));
+
+        // walls are impassable and entering a tile yields its reward
+        let tile = &grid.states()[1];
+        assert_eq!(grid.act(tile, &Move::East), tile);
+        assert_eq!(grid.act(tile, &Move::South), &grid.states()[4]);
+        assert_eq!(grid.reward(tile, &Move::South, &grid.states()[4]), -1.0);
+        assert_eq!(
+            grid.reward(&grid.states()[3], &Move::North, &grid.states()[0]),
+            -0.04
+        );
+
+        assert_eq!(grid.start_states(), &grid.states()[..1]);
+        assert_eq!(grid.reset(&mut rand::thread_rng()), &grid.states()[0]);
+
+        // every tile is a start tile of a map without a start tile
+        let grid = GridWorld::from_ascii("..G", AsciiConfig::default()).unwrap();
+        assert_eq!(grid.start_state(), None);
+        assert_eq!(grid.start_states().len(), 3);
+    }
+
+    #[test]
+    fn invalid_ascii_map() {
+        for map in ["S.\n.", "S?", "S.S", ""] {
+            let expected = if map.is_empty() {
+                MDPError::Empty
+            } else {
+                MDPError::InvalidMap
+            };
+            assert!(GridWorld::from_ascii(map, AsciiConfig::default()).err() == Some(expected));
+        }
+    }
+
     #[test]
     fn invalid_matrices() {
         // The transition matrix does not have proper dimensions
@@ -617,6 +1690,38 @@ mod tests {
         assert_eq!(grid.terminal_states.len(), 1);
     }
 
+    #[test]
+    fn checked_world() {
+        let transition_model: fn(&Move) -> fn(&Move) -> f64 = |a| match a {
+            Move::North => |d| if *d == Move::North { 1.0 } else { 0.0 },
+            Move::South => |d| if *d == Move::South { 1.0 } else { 0.0 },
+            Move::East => |d| if *d == Move::East { 1.0 } else { 0.0 },
+            Move::West => |d| if *d == Move::West { 1.0 } else { 0.0 },
+        };
+
+        // the terminal state in the corner is reachable from every other tile
+        let grid = GridWorld::from_checked(
+            3,
+            3,
+            |s| s.id == 4,
+            transition_model,
+            |_| -1.0,
+            |s| s.id == 8,
+        );
+        assert!(grid.is_ok());
+
+        // the terminal state in the corner is enclosed by walls
+        let grid = GridWorld::from_checked(
+            3,
+            3,
+            |s| s.id == 5 || s.id == 7,
+            transition_model,
+            |_| -1.0,
+            |s| s.id == 8,
+        );
+        assert_eq!(grid.err(), Some(MDPError::UnreachableTerminal { id: 0 }));
+    }
+
     #[test]
     fn corner_problem() {
         let grid = GridWorld::corner(3, 3, 0.8).unwrap();
@@ -625,4 +1730,350 @@ mod tests {
         assert_eq!(grid.columns, 3);
         assert_eq!(grid.terminal_states.len(), 2);
     }
+
+    #[test]
+    fn stochastic_rewards() {
+        // moving from the first to the second tile is rewarded around 2, while remaining in
+        // place has a fixed reward of -1
+        let probabilities = vec![
+            vec![vec![0.0, 1.0]; Move::len()],
+            vec![vec![0.0, 1.0]; Move::len()],
+        ];
+        let distributions: RewardDistributions = (0..2)
+            .map(|_| {
+                (0..Move::len())
+                    .map(|_| {
+                        vec![
+                            Box::new(-1.0) as Box<dyn RewardDistribution + Send + Sync>,
+                            Box::new(Normal::new(2.0, 0.5).unwrap()),
+                        ]
+                    })
+                    .collect()
+            })
+            .collect();
+        let grid =
+            GridWorld::with_reward_distributions(1, 2, probabilities, distributions, |s| s.id == 1)
+                .unwrap();
+
+        let (first, second) = (&grid.states()[0], &grid.states()[1]);
+        let samples: Vec<f64> = (0..10000)
+            .map(|_| grid.step(first, &Move::East).1)
+            .collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        assert!((mean - 2.0).abs() < 0.05);
+        assert!(samples.iter().any(|&reward| reward != samples[0]));
+        assert_eq!(grid.reward(first, &Move::East, first), -1.0);
+
+        // the model yields the expected rewards
+        assert_eq!(
+            grid.transitions(first, &Move::East).collect::<Vec<_>>(),
+            vec![(second, 1.0, 2.0)]
+        );
+
+        // a grid of stochastic rewards can still be shared across threads
+        let handle = std::thread::spawn(move || grid.n_states());
+        assert_eq!(handle.join().unwrap(), 2);
+    }
+
+    #[test]
+    fn windy_grid() {
+        let grid = GridWorld::windy(7, 10, vec![0, 0, 0, 1, 1, 1, 2, 2, 1, 0]).unwrap();
+        let start = grid.start_state().unwrap();
+        let goal = grid.state_at(3, 7).unwrap();
+        assert!(grid.is_terminal(goal));
+        assert_eq!(grid.start_states(), std::slice::from_ref(start));
+
+        // moving east from the fourth column is shifted one row north
+        let tile = grid.state_at(3, 3).unwrap();
+        assert_eq!(grid.act(tile, &Move::East), grid.state_at(2, 4).unwrap());
+        assert_eq!(
+            grid.reward(tile, &Move::East, grid.state_at(2, 4).unwrap()),
+            -1.0
+        );
+
+        // the wind cannot push the agent off the grid
+        let tile = grid.state_at(0, 6).unwrap();
+        assert_eq!(grid.act(tile, &Move::South), tile);
+
+        // the shortest episode of the book takes 15 steps
+        let result = ValueIteration::default()
+            .find_optimal_policy_with_values(&grid)
+            .unwrap();
+        assert_eq!(result.values[start.id], -15.0);
+
+        let grid = GridWorld::windy_with_endpoints(3, 3, vec![0, 1, 0], (2, 0), (0, 2)).unwrap();
+        assert_eq!(grid.start_state(), grid.state_at(2, 0));
+        assert!(grid.is_terminal(grid.state_at(0, 2).unwrap()));
+        assert!(matches!(
+            GridWorld::windy(3, 3, vec![0, 1]),
+            Err(MDPError::InvalidMap)
+        ));
+        assert!(matches!(
+            GridWorld::windy_with_endpoints(3, 3, vec![0, 1, 0], (3, 0), (0, 2)),
+            Err(MDPError::InvalidMap)
+        ));
+        assert!(matches!(
+            GridWorld::windy_with_endpoints(3, 3, vec![0, 1, 0], (2, 0), (0, 3)),
+            Err(MDPError::InvalidMap)
+        ));
+    }
+
+    #[test]
+    fn display() {
+        let grid = GridWorld::corner(3, 4, 0.8).unwrap();
+        let tile = grid.state_at(1, 2).unwrap();
+        assert_eq!(tile.to_string(), "(1,2)");
+        assert_eq!(tile.coordinates(), (1, 2));
+
+        let directions: Vec<String> = Move::ACTIONS.iter().map(Move::to_string).collect();
+        assert_eq!(directions, vec!["N", "S", "E", "W"]);
+    }
+
+    #[test]
+    fn move_from_id() {
+        for action in Move::ACTIONS.iter() {
+            assert_eq!(Move::from_id(action.id()).as_ref(), Some(action));
+        }
+        assert_eq!(Move::from_id(Move::len()), None);
+    }
+
+    #[test]
+    fn coordinates() {
+        let grid = GridWorld::corner(3, 4, 0.8).unwrap();
+
+        let tile = grid.state_at(1, 2).unwrap();
+        // the ID is row * columns + col
+        assert_eq!(tile.id, 6);
+        assert_eq!((tile.x, tile.y), (1, 2));
+        assert_eq!(grid.index_of(2, 3), 11);
+        assert_eq!(grid.state_at(3, 0), None);
+        assert_eq!(grid.state_at(0, 4), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid column value: 4")]
+    fn invalid_coordinates() {
+        let grid = GridWorld::corner(3, 4, 0.8).unwrap();
+        grid.index_of(0, 4);
+    }
+
+    #[test]
+    fn degenerate_transitions() {
+        let mut grid = GridWorld::corner(3, 3, 0.8).unwrap();
+        grid.transition_probabilities[4][Move::North.id()] = vec![0.0; 9];
+
+        let state = &grid.states()[4];
+        assert_eq!(grid.act(state, &Move::North), state);
+
+        // the highest-id successor having a valid probability is chosen
+        grid.transition_probabilities[4][Move::North.id()] =
+            vec![0.0, f64::NAN, 0.0, 0.5, 0.0, 0.5, 0.0, -0.5, 0.0];
+        let state = &grid.states()[4];
+        assert_eq!(grid.act(state, &Move::North), &grid.states()[5]);
+    }
+
+    #[test]
+    fn reward_validation() {
+        let mut grid = GridWorld::corner(3, 3, 0.8).unwrap();
+        assert!(grid.validate_rewards().is_ok());
+
+        // bumping into the north edge of tile 1 is rewarding
+        grid.rewards[1][Move::North.id()][1] = 1.0;
+        assert_eq!(
+            grid.validate_rewards(),
+            Err(MDPError::PositiveRewardCycle {
+                state: &grid.states()[1]
+            })
+        );
+    }
+
+    #[test]
+    fn grid_equality() {
+        let grid = GridWorld::corner(3, 3, 0.8).unwrap();
+        let mut other = grid.clone();
+        assert!(grid == other);
+
+        // differences within the tolerance are ignored
+        other.rewards[4][Move::North.id()][1] += 1e-12;
+        assert!(grid == other);
+
+        other.rewards[4][Move::North.id()][1] += 1.0;
+        assert!(grid != other);
+        assert!(grid != GridWorld::corner(3, 3, 0.7).unwrap());
+        assert!(grid != GridWorld::corner(3, 4, 0.8).unwrap());
+    }
+
+    #[test]
+    fn render_solution() {
+        let grid = GridWorld::from(
+            1,
+            3,
+            |s| s.id == 1,
+            |a| match a {
+                Move::North => |d| if *d == Move::North { 1.0 } else { 0.0 },
+                Move::South => |d| if *d == Move::South { 1.0 } else { 0.0 },
+                Move::East => |d| if *d == Move::East { 1.0 } else { 0.0 },
+                Move::West => |d| if *d == Move::West { 1.0 } else { 0.0 },
+            },
+            |_| -1.0,
+            |s| s.id == 2,
+        )
+        .unwrap();
+        let policy = Policy::new(HashMap::from([(&grid.states()[0], &Move::East)]));
+
+        assert_eq!(
+            grid.render_solution(&policy, &[-1.5, 0.0, 0.0]),
+            "+-------------+-------------+-------------+\n\
+             |      →      |#############|     =T=     |\n\
+             |    -1.50    |#############|    0.00     |\n\
+             +-------------+-------------+-------------+\n"
+        );
+    }
+
+    #[test]
+    fn render_policy() {
+        let grid = GridWorld::from_ascii("S.#\n..G", AsciiConfig::default()).unwrap();
+        let states = grid.states();
+        let policy = Policy::new(HashMap::from([
+            (&states[0], &Move::South),
+            (&states[3], &Move::East),
+            (&states[4], &Move::East),
+        ]));
+
+        assert_eq!(
+            grid.render_policy(&policy),
+            "+-------------+-------------+-------------+\n\
+             |      ↓      |      ?      |#############|\n\
+             |             |             |#############|\n\
+             +-------------+-------------+-------------+\n\
+             |      →      |      →      |      T      |\n\
+             |             |             |             |\n\
+             +-------------+-------------+-------------+\n"
+        );
+    }
+
+    #[test]
+    fn available_actions() {
+        let grid = GridWorld::corner(3, 3, 0.8).unwrap();
+        let states = grid.states();
+
+        // moving north from the top row only bumps into the edge
+        assert!(!grid.is_action_available(&states[1], &Move::North));
+        assert!(grid.is_action_available(&states[1], &Move::South));
+        assert!(grid.is_action_available(&states[1], &Move::East));
+        assert!(grid.is_action_available(&states[1], &Move::West));
+
+        // every action remains available on terminal states
+        assert!(Move::ACTIONS
+            .iter()
+            .all(|action| grid.is_action_available(&states[0], action)));
+
+        // moves into walls are masked as well
+        let grid = GridWorld::from_ascii("S#\n.G", AsciiConfig::default()).unwrap();
+        assert_eq!(
+            grid.available_actions(&grid.states()[0]),
+            vec![&Move::South]
+        );
+    }
+
+    #[test]
+    fn tile_features() {
+        let grid = GridWorld::corner(3, 2, 0.8).unwrap();
+        assert_eq!(grid.n_features(), 6);
+        assert_eq!(
+            grid.features(&grid.states()[3]),
+            vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0]
+        );
+
+        let grid = grid.with_encoding(TileEncoding::Coordinates);
+        assert_eq!(grid.n_features(), 2);
+        assert_eq!(grid.features(&grid.states()[3]), vec![0.5, 1.0]);
+        assert_eq!(grid.features(&grid.states()[0]), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn horizon_for_confidence() {
+        let grid = GridWorld::corner(1, 4, 0.5).unwrap();
+        let policy = Policy::new(HashMap::from([
+            (&grid.states()[1], &Move::West),
+            (&grid.states()[2], &Move::East),
+        ]));
+        let states = grid.states();
+
+        // each step succeeds with probability 0.5, so after H steps the terminal is reached
+        // with probability 1 - 0.5^H
+        assert_eq!(
+            grid.horizon_for_confidence(&policy, &states[1], 0.5, 100),
+            Some(1)
+        );
+        assert_eq!(
+            grid.horizon_for_confidence(&policy, &states[1], 0.9, 100),
+            Some(4)
+        );
+        assert_eq!(
+            grid.horizon_for_confidence(&policy, &states[1], 0.9, 3),
+            None
+        );
+        assert_eq!(
+            grid.horizon_for_confidence(&policy, &states[0], 1.0, 0),
+            Some(0)
+        );
+
+        // without actions the terminal is never reached
+        let empty = Policy::new(HashMap::new());
+        assert_eq!(
+            grid.horizon_for_confidence(&empty, &states[1], 0.1, 100),
+            None
+        );
+    }
+
+    #[test]
+    fn shortest_path() {
+        let transition_model: fn(&Move) -> fn(&Move) -> f64 = |a| match a {
+            Move::North => |d| if *d == Move::North { 1.0 } else { 0.0 },
+            Move::South => |d| if *d == Move::South { 1.0 } else { 0.0 },
+            Move::East => |d| if *d == Move::East { 1.0 } else { 0.0 },
+            Move::West => |d| if *d == Move::West { 1.0 } else { 0.0 },
+        };
+        let grid = GridWorld::from(
+            3,
+            3,
+            |s| s.id == 4,
+            transition_model,
+            |_| -1.0,
+            |s| s.id == 8,
+        )
+        .unwrap();
+        let (start, goal) = (&grid.states()[0], &grid.states()[8]);
+
+        // the path goes around the wall in the center
+        let path = grid
+            .shortest_path(start, goal, GridWorld::manhattan_distance)
+            .unwrap();
+        assert_eq!(path.len(), 5);
+        assert_eq!((path[0], path[4]), (start, goal));
+        assert!(path.iter().all(|tile| tile.id != 4));
+
+        // an uninformed search and an inflated heuristic find equally long paths
+        let dijkstra = grid.shortest_path(start, goal, |_, _| 0.0).unwrap();
+        assert_eq!(dijkstra.len(), 5);
+        let weighted = grid
+            .weighted_a_star(start, goal, GridWorld::manhattan_distance, 5.0)
+            .unwrap();
+        assert_eq!(weighted.len(), 5);
+
+        // the terminal state is a dead end
+        assert_eq!(
+            grid.shortest_path(goal, start, GridWorld::manhattan_distance),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid weight value: 0.5")]
+    fn underweighted_a_star() {
+        let grid = GridWorld::corner(2, 2, 0.8).unwrap();
+        let states = grid.states();
+        grid.weighted_a_star(&states[0], &states[3], GridWorld::manhattan_distance, 0.5);
+    }
 }
@@ -1,4 +1,6 @@
-use crate::mdp::policy::Policy;
+use crate::mdp::policy::{Policy, StochasticPolicy};
+use rand::seq::SliceRandom;
+use rand::Rng;
 use std::error::Error;
 use std::fmt;
 use std::fmt::Debug;
@@ -11,6 +13,10 @@ pub enum MDPError<'a, S: State> {
     NoTransition { state: &'a S },
     InvalidTransitionMatrix,
     InvalidRewardMatrix,
+    UnreachableTerminal { id: usize },
+    PositiveRewardCycle { state: &'a S },
+    InvalidMap,
+    Unsolvable,
 }
 
 impl<'a, S: State> Error for MDPError<'a, S> {}
@@ -31,17 +37,74 @@ impl<'a, S: State> fmt::Display for MDPError<'a, S> {
             MDPError::InvalidRewardMatrix => {
                 write!(f, "The reward matrix has invalid dimensions.")
             }
+            MDPError::UnreachableTerminal { id } => {
+                write!(f, "No terminal state is reachable from state {id}.")
+            }
+            MDPError::PositiveRewardCycle { state } => {
+                write!(f, "State {} has a positive self-loop reward, which yields an infinite value without discount.", state.id())
+            }
+            MDPError::InvalidMap => {
+                write!(f, "The map is invalid. Either the lines have different lengths, a tile is unknown or there are several start tiles.")
+            }
+            MDPError::Unsolvable => {
+                write!(f, "The MDP has no bounded optimal values, e.g., due to unreachable terminal states without discount.")
+            }
         }
     }
 }
 
 /// Represents an episode of an MDP. Each such episode has a starting state, a trajectory
-/// of states that the agent navigated in the MDP horizon and a total reward.
+/// of states that the agent navigated in the MDP horizon and a total reward. The total reward
+/// is the discounted return `Σ_t γ^t r_t`, while the undiscounted reward is the plain sum of
+/// the rewards, and thus the two coincide for undiscounted MDPs.
 #[derive(Debug)]
 pub struct Episode<'a, S: State> {
     pub starting_state: &'a S,
     pub trajectory: Vec<&'a S>,
     pub total_reward: f64,
+    pub undiscounted_reward: f64,
+}
+
+impl<'a, S: State> Clone for Episode<'a, S> {
+    fn clone(&self) -> Self {
+        Episode {
+            starting_state: self.starting_state,
+            trajectory: self.trajectory.clone(),
+            total_reward: self.total_reward,
+            undiscounted_reward: self.undiscounted_reward,
+        }
+    }
+}
+
+/// Represents the best and the worst episodes, in terms of total reward, among a number of
+/// rollouts. Inspecting concrete extreme episodes is often more revealing than aggregate
+/// statistics when debugging policies in stochastic environments.
+#[derive(Debug)]
+pub struct BestWorst<'a, S: State> {
+    pub best: Episode<'a, S>,
+    pub worst: Episode<'a, S>,
+}
+
+impl<'a, S: State> BestWorst<'a, S> {
+    /// Selects the episodes having the highest and the lowest total reward, or none if
+    /// there are no episodes.
+    ///
+    /// # Arguments
+    ///
+    /// - `episodes` - the episodes to select from.
+    pub fn select(episodes: &[Episode<'a, S>]) -> Option<Self> {
+        let best = episodes
+            .iter()
+            .max_by(|a, b| a.total_reward.total_cmp(&b.total_reward))?;
+        let worst = episodes
+            .iter()
+            .min_by(|a, b| a.total_reward.total_cmp(&b.total_reward))?;
+
+        Some(BestWorst {
+            best: best.clone(),
+            worst: worst.clone(),
+        })
+    }
 }
 
 /// Represents a state in the MDP. Each state should have a unique index or ID,
@@ -100,6 +163,22 @@ pub trait MDP<S: State, A: Action> {
     /// Returns true if the given state is a terminal.
     fn is_terminal(&self, state: &S) -> bool;
 
+    /// Returns the states an episode may start from. By default every state is a start state.
+    fn start_states(&self) -> &[S] {
+        self.states()
+    }
+
+    /// Samples a start state uniformly at random from the start states.
+    ///
+    /// # Arguments
+    ///
+    /// - `rng` - the random number generator used to sample the start state.
+    fn reset<R: Rng + ?Sized>(&self, rng: &mut R) -> &S {
+        self.start_states()
+            .choose(rng)
+            .expect("Start states must not be empty")
+    }
+
     /// Discount factor determines the value of future rewards. By default
     /// this function always returns 1, which accounts for no discount.
     #[inline(always)]
@@ -107,14 +186,102 @@ pub trait MDP<S: State, A: Action> {
         1.0
     }
 
+    /// Returns true if the given action is available in the given state. By default
+    /// every action is available in every state. Implementations may override it to
+    /// report actions that are pointless in a state, so that optimizers can skip them.
+    #[inline(always)]
+    fn is_action_available(&self, _state: &S, _action: &A) -> bool {
+        true
+    }
+
+    /// Returns the actions available in the given state. By default these are the actions
+    /// for which [`MDP::is_action_available`] holds, that is, all actions unless overridden.
+    /// Optimizers only consider the available actions of each state.
+    ///
+    /// # Arguments
+    ///
+    /// - `state` - the state of interest.
+    fn available_actions(&self, state: &S) -> Vec<&A> {
+        self.actions()
+            .iter()
+            .filter(|action| self.is_action_available(state, action))
+            .collect()
+    }
+
     /// Returns the transition probability of the triplet (state, action, state).
     fn transition_probability(&self, state: &S, action: &A, next_state: &S) -> f64;
 
-    /// Returns the reward for the triplet (state, action, state).
+    /// Returns the reward for the triplet (state, action, state). For environments having
+    /// stochastic rewards, this is a sample of the reward, while [`MDP::transitions`] should
+    /// yield its mean.
     fn reward(&self, state: &S, action: &A, next_state: &S) -> f64;
 
-    /// Acts on the given state using the given action and returns the next state.
-    fn act(&self, state: &S, action: &A) -> &S;
+    /// Returns the possible transitions of taking the given action in the given state, that
+    /// is, every next state having a positive probability along with the probability and the
+    /// reward of the transition. By default all states are scanned, while implementations
+    /// having sparse transitions, e.g., grids, may override it to speed up the optimizers.
+    ///
+    /// # Arguments
+    ///
+    /// - `state` - the current state.
+    /// - `action` - the action to take.
+    fn transitions<'a>(
+        &'a self,
+        state: &'a S,
+        action: &'a A,
+    ) -> impl Iterator<Item = (&'a S, f64, f64)> + 'a {
+        self.states().iter().filter_map(move |next_state| {
+            let p = self.transition_probability(state, action, next_state);
+            (p > 0.0).then(|| (next_state, p, self.reward(state, action, next_state)))
+        })
+    }
+
+    /// Returns the possible transitions of taking the given action in the given state as a
+    /// table of next state ID, probability and reward triplets. By default it collects
+    /// [`MDP::transitions`], which the optimizers iterate directly to avoid the allocation.
+    ///
+    /// # Arguments
+    ///
+    /// - `state` - the current state.
+    /// - `action` - the action to take.
+    fn transition_table(&self, state: &S, action: &A) -> Vec<(usize, f64, f64)> {
+        self.transitions(state, action)
+            .map(|(next_state, p, r)| (next_state.id(), p, r))
+            .collect()
+    }
+
+    /// Acts on the given state using the given action and returns the next state, which is
+    /// sampled using the given random number generator.
+    ///
+    /// # Arguments
+    ///
+    /// - `state` - the current state.
+    /// - `action` - the action to take.
+    /// - `rng` - the random number generator used to sample the next state.
+    fn act_with<R: Rng + ?Sized>(&self, state: &S, action: &A, rng: &mut R) -> &S;
+
+    /// Acts on the given state using the given action and returns the next state, which is
+    /// sampled using the thread-local generator.
+    fn act(&self, state: &S, action: &A) -> &S {
+        self.act_with(state, action, &mut rand::thread_rng())
+    }
+
+    /// Acts on the given state using the given action and returns the next state, the reward
+    /// of the transition and whether the next state is terminal, following the common
+    /// `(observation, reward, done)` convention of reinforcement learning environments.
+    ///
+    /// # Arguments
+    ///
+    /// - `state` - the current state.
+    /// - `action` - the action to take.
+    fn step(&self, state: &S, action: &A) -> (&S, f64, bool) {
+        let next_state = self.act(state, action);
+        (
+            next_state,
+            self.reward(state, action, next_state),
+            self.is_terminal(next_state),
+        )
+    }
 
     /// Executes a given policy on the MDP and returns an episode.
     ///
@@ -129,17 +296,147 @@ pub trait MDP<S: State, A: Action> {
         starting_state: &'a S,
         maximum_steps: usize,
     ) -> Result<Episode<'a, S>, MDPError<'a, S>> {
+        self.run_policy_noisy(
+            policy,
+            starting_state,
+            maximum_steps,
+            1.0,
+            &mut rand::thread_rng(),
+        )
+    }
+
+    /// Executes a given policy on the MDP from a sampled start state and returns an episode.
+    ///
+    /// # Arguments
+    ///
+    /// - `policy` - the policy to be executed.
+    /// - `maximum_steps` - the maximum iterations for the execution. If no terminal state is achieved the execution terminates.
+    fn run_episode<'a>(
+        &'a self,
+        policy: &'a Policy<S, A>,
+        maximum_steps: usize,
+    ) -> Result<Episode<'a, S>, MDPError<'a, S>> {
+        let starting_state = self.reset(&mut rand::thread_rng());
+        self.run_policy(policy, starting_state, maximum_steps)
+    }
+
+    /// Executes a given policy on the MDP a number of times and returns all episodes.
+    ///
+    /// # Arguments
+    ///
+    /// - `policy` - the policy to be executed.
+    /// - `starting_state` - the init state of the MDP, that is, the state that the agent starts.
+    /// - `maximum_steps` - the maximum iterations for each execution.
+    /// - `n` - the number of episodes.
+    /// - `rng` - the random number generator used by the executions.
+    fn sample_episodes<'a, R: Rng + ?Sized>(
+        &'a self,
+        policy: &'a Policy<S, A>,
+        starting_state: &'a S,
+        maximum_steps: usize,
+        n: usize,
+        rng: &mut R,
+    ) -> Result<Vec<Episode<'a, S>>, MDPError<'a, S>> {
+        (0..n)
+            .map(|_| self.run_policy_noisy(policy, starting_state, maximum_steps, 1.0, rng))
+            .collect()
+    }
+
+    /// Executes a given stochastic policy on the MDP and returns an episode. At each step, the
+    /// action is sampled from the action probabilities of the policy in the current state.
+    ///
+    /// # Arguments
+    ///
+    /// - `policy` - the stochastic policy to be executed.
+    /// - `starting_state` - the init state of the MDP, that is, the state that the agent starts.
+    /// - `maximum_steps` - the maximum iterations for the execution. If no terminal state is achieved the execution terminates.
+    /// - `rng` - the random number generator used to sample actions and next states.
+    fn run_stochastic_policy<'a, R: Rng + ?Sized>(
+        &'a self,
+        policy: &StochasticPolicy<S, A>,
+        starting_state: &'a S,
+        maximum_steps: usize,
+        rng: &mut R,
+    ) -> Result<Episode<'a, S>, MDPError<'a, S>> {
+        let mut total_reward = 0f64;
+        let mut undiscounted_reward = 0f64;
+        let mut discount = 1f64;
+        let mut trajectory = vec![starting_state];
+        let mut state = starting_state;
+
+        for _ in 0..maximum_steps {
+            let action = match policy.sample_action(state, rng) {
+                Some(action) => action,
+                None => return Err(MDPError::NoAction { state }),
+            };
+
+            let next_state = self.act_with(state, action, rng);
+            trajectory.push(next_state);
+            let reward = self.reward(state, action, next_state);
+            total_reward += discount * reward;
+            undiscounted_reward += reward;
+            discount *= self.discount_factor();
+            state = next_state;
+
+            if self.is_terminal(state) {
+                break;
+            }
+        }
+
+        Ok(Episode {
+            starting_state,
+            trajectory,
+            total_reward,
+            undiscounted_reward,
+        })
+    }
+
+    /// Executes a given policy on the MDP as a noisy rational agent and returns an episode.
+    /// At each step, the agent follows the policy with probability `rationality`, otherwise
+    /// it takes an action uniformly at random.
+    ///
+    /// # Arguments
+    ///
+    /// - `policy` - the policy to be executed.
+    /// - `starting_state` - the init state of the MDP, that is, the state that the agent starts.
+    /// - `maximum_steps` - the maximum iterations for the execution. If no terminal state is achieved the execution terminates.
+    /// - `rationality` - the probability of following the policy, between 0 and 1.
+    /// - `rng` - the random number generator used to select random actions and sample next states.
+    fn run_policy_noisy<'a, R: Rng + ?Sized>(
+        &'a self,
+        policy: &'a Policy<S, A>,
+        starting_state: &'a S,
+        maximum_steps: usize,
+        rationality: f64,
+        rng: &mut R,
+    ) -> Result<Episode<'a, S>, MDPError<'a, S>> {
+        if !(0.0..=1.0).contains(&rationality) {
+            panic!("Invalid rationality value: {rationality}");
+        }
+
         let mut total_reward = 0f64;
+        let mut undiscounted_reward = 0f64;
+        let mut discount = 1f64;
         let mut trajectory = vec![starting_state];
         let mut state = starting_state;
 
         for _ in 0..maximum_steps {
-            // select policy action and place the agent to the next state
-            match policy.select_action(state) {
+            // select either a random or the policy action
+            let action = if rng.gen_bool(1.0 - rationality) {
+                self.actions().choose(rng)
+            } else {
+                policy.select_action(state)
+            };
+
+            // place the agent to the next state
+            match action {
                 Some(action) => {
-                    let next_state = self.act(state, action);
+                    let next_state = self.act_with(state, action, rng);
                     trajectory.push(next_state);
-                    total_reward += self.reward(state, action, next_state);
+                    let reward = self.reward(state, action, next_state);
+                    total_reward += discount * reward;
+                    undiscounted_reward += reward;
+                    discount *= self.discount_factor();
                     state = next_state;
                 }
                 None => {
@@ -157,17 +454,282 @@ pub trait MDP<S: State, A: Action> {
             starting_state,
             trajectory,
             total_reward,
+            undiscounted_reward,
         })
     }
 }
 
+/// Represents an environment that can describe each of its states by a fixed-length vector
+/// of real-valued features. Feature vectors are the input of function approximation methods,
+/// which generalize across states instead of learning a separate value for each one.
+pub trait FeatureEncoder<S: State> {
+    /// Returns the number of features, that is, the length of every feature vector.
+    fn n_features(&self) -> usize;
+
+    /// Returns the feature vector of the given state.
+    fn features(&self, state: &S) -> Vec<f64>;
+}
+
+/// Wraps an MDP and applies a transformation to every reward it returns, while delegating
+/// everything else to the inner MDP. It can be used to study how reward engineering affects
+/// the optimal policy, e.g., the policy should be invariant to positive scaling.
+///
+/// # Examples
+///
+/// ```
+/// use readapt::mdp::environment::GridWorld;
+/// use readapt::mdp::model::{TransformedMDP, MDP};
+///
+/// let scaled = TransformedMDP::new(GridWorld::corner(3, 3, 0.8).unwrap(), |r| 10.0 * r);
+///
+/// let (state, action, next_state) = (&scaled.states()[1], &scaled.actions()[3], &scaled.states()[0]);
+/// assert_eq!(scaled.reward(state, action, next_state), -10.0);
+/// ```
+pub struct TransformedMDP<M, F: Fn(f64) -> f64> {
+    mdp: M,
+    transform: F,
+}
+
+impl<M, F: Fn(f64) -> f64> TransformedMDP<M, F> {
+    /// Creates a transformed MDP.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - the inner MDP.
+    /// - `transform` - the function applied to every reward of the inner MDP.
+    pub fn new(mdp: M, transform: F) -> Self {
+        Self { mdp, transform }
+    }
+}
+
+impl<S: State, A: Action, M: MDP<S, A>, F: Fn(f64) -> f64> MDP<S, A> for TransformedMDP<M, F> {
+    fn n_states(&self) -> usize {
+        self.mdp.n_states()
+    }
+
+    fn states(&self) -> &[S] {
+        self.mdp.states()
+    }
+
+    fn n_actions(&self) -> usize {
+        self.mdp.n_actions()
+    }
+
+    fn actions(&self) -> &[A] {
+        self.mdp.actions()
+    }
+
+    fn is_terminal(&self, state: &S) -> bool {
+        self.mdp.is_terminal(state)
+    }
+
+    fn start_states(&self) -> &[S] {
+        self.mdp.start_states()
+    }
+
+    fn discount_factor(&self) -> f64 {
+        self.mdp.discount_factor()
+    }
+
+    fn is_action_available(&self, state: &S, action: &A) -> bool {
+        self.mdp.is_action_available(state, action)
+    }
+
+    fn available_actions(&self, state: &S) -> Vec<&A> {
+        self.mdp.available_actions(state)
+    }
+
+    fn transition_probability(&self, state: &S, action: &A, next_state: &S) -> f64 {
+        self.mdp.transition_probability(state, action, next_state)
+    }
+
+    fn reward(&self, state: &S, action: &A, next_state: &S) -> f64 {
+        (self.transform)(self.mdp.reward(state, action, next_state))
+    }
+
+    fn transitions<'a>(
+        &'a self,
+        state: &'a S,
+        action: &'a A,
+    ) -> impl Iterator<Item = (&'a S, f64, f64)> + 'a {
+        self.mdp
+            .transitions(state, action)
+            .map(|(next_state, p, r)| (next_state, p, (self.transform)(r)))
+    }
+
+    fn act_with<R: Rng + ?Sized>(&self, state: &S, action: &A, rng: &mut R) -> &S {
+        self.mdp.act_with(state, action, rng)
+    }
+}
+
+/// Wraps an MDP and applies potential-based reward shaping, that is, every reward is augmented
+/// by `γ Φ(s') - Φ(s)`, where `Φ` is a potential function over states. Potential-based shaping
+/// may speed up learning, e.g., by encoding the distance to a goal, while provably preserving
+/// the optimal policies of the inner MDP. The potential of terminal states is taken to be zero,
+/// as required for the guarantee to hold in episodic tasks.
+///
+/// # Examples
+///
+/// ```
+/// use readapt::mdp::environment::{GridWorld, Tile};
+/// use readapt::mdp::model::{ShapedMDP, MDP};
+///
+/// let shaped = ShapedMDP::new(GridWorld::corner(3, 3, 0.8).unwrap(), |tile: &Tile| -(tile.x as f64));
+///
+/// let (state, action, next_state) = (&shaped.states()[4], &shaped.actions()[0], &shaped.states()[1]);
+/// assert_eq!(shaped.reward(state, action, next_state), 0.0);
+/// ```
+pub struct ShapedMDP<M, P> {
+    mdp: M,
+    potential: P,
+}
+
+impl<M, P> ShapedMDP<M, P> {
+    /// Creates a shaped MDP.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - the inner MDP.
+    /// - `potential` - the potential function over states.
+    pub fn new(mdp: M, potential: P) -> Self {
+        Self { mdp, potential }
+    }
+
+    /// Returns the inner MDP.
+    pub fn inner(&self) -> &M {
+        &self.mdp
+    }
+
+    /// Returns the shaping term `γΦ(s') - Φ(s)` added to the reward of a transition.
+    fn shaping<S: State, A: Action>(&self, state: &S, next_state: &S) -> f64
+    where
+        M: MDP<S, A>,
+        P: Fn(&S) -> f64,
+    {
+        // the potential of terminal states is zero
+        let phi = |s: &S| {
+            if self.mdp.is_terminal(s) {
+                0.0
+            } else {
+                (self.potential)(s)
+            }
+        };
+
+        self.mdp.discount_factor() * phi(next_state) - phi(state)
+    }
+}
+
+impl<S: State, A: Action, M: MDP<S, A>, P: Fn(&S) -> f64> MDP<S, A> for ShapedMDP<M, P> {
+    fn n_states(&self) -> usize {
+        self.mdp.n_states()
+    }
+
+    fn states(&self) -> &[S] {
+        self.mdp.states()
+    }
+
+    fn n_actions(&self) -> usize {
+        self.mdp.n_actions()
+    }
+
+    fn actions(&self) -> &[A] {
+        self.mdp.actions()
+    }
+
+    fn is_terminal(&self, state: &S) -> bool {
+        self.mdp.is_terminal(state)
+    }
+
+    fn start_states(&self) -> &[S] {
+        self.mdp.start_states()
+    }
+
+    fn discount_factor(&self) -> f64 {
+        self.mdp.discount_factor()
+    }
+
+    fn is_action_available(&self, state: &S, action: &A) -> bool {
+        self.mdp.is_action_available(state, action)
+    }
+
+    fn available_actions(&self, state: &S) -> Vec<&A> {
+        self.mdp.available_actions(state)
+    }
+
+    fn transition_probability(&self, state: &S, action: &A, next_state: &S) -> f64 {
+        self.mdp.transition_probability(state, action, next_state)
+    }
+
+    fn reward(&self, state: &S, action: &A, next_state: &S) -> f64 {
+        self.mdp.reward(state, action, next_state) + self.shaping::<S, A>(state, next_state)
+    }
+
+    fn transitions<'a>(
+        &'a self,
+        state: &'a S,
+        action: &'a A,
+    ) -> impl Iterator<Item = (&'a S, f64, f64)> + 'a {
+        self.mdp
+            .transitions(state, action)
+            .map(|(next_state, p, r)| (next_state, p, r + self.shaping::<S, A>(state, next_state)))
+    }
+
+    fn act_with<R: Rng + ?Sized>(&self, state: &S, action: &A, rng: &mut R) -> &S {
+        self.mdp.act_with(state, action, rng)
+    }
+}
+
+/// Estimates the transition model of an MDP by sampling. For each state-action pair, the
+/// MDP acts a number of times and the empirical next-state frequencies are tallied into a
+/// matrix of dimension SxAxS, indexed by state and action IDs. The estimate can be compared
+/// against the true transition probabilities to confirm that `act` samples correctly.
+///
+/// # Arguments
+///
+/// - `mdp` - the MDP whose transitions are sampled.
+/// - `samples_per_pair` - the number of samples drawn for each state-action pair.
+/// - `rng` - the random number generator used to sample the next states.
+pub fn estimate_transition_model<S: State, A: Action, M: MDP<S, A>, R: Rng + ?Sized>(
+    mdp: &M,
+    samples_per_pair: usize,
+    rng: &mut R,
+) -> Vec<Vec<Vec<f64>>> {
+    let mut model = vec![vec![vec![0.0; mdp.n_states()]; mdp.n_actions()]; mdp.n_states()];
+
+    if samples_per_pair == 0 {
+        return model;
+    }
+
+    for state in mdp.states() {
+        for action in mdp.actions() {
+            let frequencies = &mut model[state.id()][action.id()];
+            for _ in 0..samples_per_pair {
+                frequencies[mdp.act_with(state, action, rng).id()] += 1.0;
+            }
+            frequencies
+                .iter_mut()
+                .for_each(|f| *f /= samples_per_pair as f64);
+        }
+    }
+
+    model
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use crate::mdp::model::{Action, State, MDP};
-    use crate::mdp::policy::Policy;
-    use rand::Rng;
+    use crate::mdp::environment::{
+        AsciiConfig, GridWorld, Move, RewardDistribution, RewardDistributions, Tile,
+    };
+    use crate::mdp::model::{
+        estimate_transition_model, Action, BestWorst, ShapedMDP, State, TransformedMDP, MDP,
+    };
+    use crate::mdp::optimizer::{Optimizer, ValueIteration};
+    use crate::mdp::policy::{EpsilonGreedyPolicy, Policy, StochasticPolicy};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use rand_distr::Normal;
 
     #[derive(Debug, Hash, PartialEq, Eq)]
     struct S {
@@ -218,8 +780,8 @@ mod tests {
             state.id() == self.n_states() - 1
         }
 
-        fn act(&self, state: &S, _: &A) -> &S {
-            if rand::thread_rng().gen_bool(0.5) {
+        fn act_with<R: Rng + ?Sized>(&self, state: &S, _: &A, rng: &mut R) -> &S {
+            if rng.gen_bool(0.5) {
                 // forward
                 if state.id() != self.n_states() - 1 {
                     &self.states[state.id() + 1]
@@ -250,6 +812,87 @@ mod tests {
         }
     }
 
+    #[test]
+    fn step() {
+        let env = Line {
+            states: (0..2).map(|id| S { id }).collect(),
+            actions: vec![A::Forward, A::Backward],
+        };
+
+        // from the first state the line either moves forward to the terminal or stays
+        for _ in 0..10 {
+            match env.step(&env.states[0], &A::Forward) {
+                (next_state, reward, done) if next_state.id() == 1 => {
+                    assert_eq!((reward, done), (0.0, true));
+                }
+                (next_state, reward, done) => {
+                    assert_eq!((next_state.id(), reward, done), (0, -1.0, false));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn run_stochastic_policy() {
+        let grid = GridWorld::corner(3, 3, 1.0).unwrap();
+        let states = grid.states();
+        let policy = StochasticPolicy::new(
+            states
+                .iter()
+                .map(|state| (state, vec![(&Move::North, 0.5), (&Move::West, 0.5)]))
+                .collect(),
+        );
+
+        // moving either north or west from the center eventually reaches the upper-left corner
+        let episode = grid
+            .run_stochastic_policy(&policy, &states[4], 1000, &mut rand::thread_rng())
+            .unwrap();
+        assert_eq!(episode.trajectory.last().unwrap().id(), 0);
+    }
+
+    #[test]
+    fn run_epsilon_greedy_policy() {
+        let grid = GridWorld::corner(4, 4, 1.0).unwrap();
+        let optimal = ValueIteration::default()
+            .find_optimal_policy(&grid)
+            .unwrap();
+        let policy = StochasticPolicy::from(EpsilonGreedyPolicy::new(optimal, 0.0, grid.actions()));
+
+        // without exploration the converged policy reaches a corner in the fewest steps
+        let episode = grid
+            .run_stochastic_policy(&policy, &grid.states()[5], 100, &mut rand::thread_rng())
+            .unwrap();
+        assert_eq!(episode.total_reward, -2.0);
+    }
+
+    #[test]
+    fn discounted_return() {
+        let grid = GridWorld::corner(1, 5, 1.0)
+            .unwrap()
+            .with_discount_factor(0.5);
+        let states = grid.states();
+        let policy = Policy::new(states.iter().map(|state| (state, &Move::West)).collect());
+
+        // moving west from the center takes two steps, each having a reward of -1, where the
+        // second one is discounted by half
+        let episode = grid.run_policy(&policy, &states[2], 10).unwrap();
+        assert_eq!(episode.trajectory.len(), 3);
+        assert_eq!(episode.total_reward, -1.0 - 0.5);
+        assert_eq!(episode.undiscounted_reward, -2.0);
+    }
+
+    #[test]
+    fn run_episode() {
+        let grid = GridWorld::from_ascii("S.G", AsciiConfig::default()).unwrap();
+        let states = grid.states();
+        let policy = Policy::new(states.iter().map(|state| (state, &Move::East)).collect());
+
+        // episodes always start from the start tile of the map
+        let episode = grid.run_episode(&policy, 10).unwrap();
+        assert_eq!(episode.starting_state, &states[0]);
+        assert_eq!(episode.trajectory.len(), 3);
+    }
+
     #[test]
     fn run_incomplete_policy() {
         let env = Line {
@@ -268,6 +911,73 @@ mod tests {
             .contains("No action available for state 0."));
     }
 
+    #[test]
+    fn run_noisy_policy() {
+        let env = Line {
+            states: (0..10).map(|id| S { id }).collect(),
+            actions: vec![A::Forward, A::Backward],
+        };
+
+        // a completely irrational agent never consults the policy, so it cannot fail
+        let empty_policy = Policy::new(HashMap::new());
+        let episode = env.run_policy_noisy(
+            &empty_policy,
+            &env.states[0],
+            10,
+            0.0,
+            &mut rand::thread_rng(),
+        );
+        assert!(episode.is_ok());
+
+        // a perfectly rational agent always consults the policy
+        let episode = env.run_policy_noisy(
+            &empty_policy,
+            &env.states[0],
+            10,
+            1.0,
+            &mut rand::thread_rng(),
+        );
+        assert!(episode.is_err());
+    }
+
+    #[test]
+    fn best_and_worst_episodes() {
+        let env = Line {
+            states: (0..5).map(|id| S { id }).collect(),
+            actions: vec![A::Forward, A::Backward],
+        };
+
+        let policy = Policy::random(&env.states, &env.actions);
+        let episodes = env
+            .sample_episodes(&policy, &env.states[0], 20, 50, &mut rand::thread_rng())
+            .unwrap();
+        assert_eq!(episodes.len(), 50);
+
+        let extremes = BestWorst::select(&episodes).unwrap();
+        assert!(episodes
+            .iter()
+            .all(|episode| episode.total_reward <= extremes.best.total_reward
+                && episode.total_reward >= extremes.worst.total_reward));
+
+        assert!(BestWorst::<S>::select(&[]).is_none());
+
+        // a seeded generator samples the same trajectories
+        let trajectories = |seed| -> Vec<Vec<usize>> {
+            env.sample_episodes(
+                &policy,
+                &env.states[0],
+                20,
+                10,
+                &mut StdRng::seed_from_u64(seed),
+            )
+            .unwrap()
+            .iter()
+            .map(|episode| episode.trajectory.iter().map(|state| state.id()).collect())
+            .collect()
+        };
+        assert_eq!(trajectories(3), trajectories(3));
+    }
+
     #[test]
     fn run_random_policy() {
         let env = Line {
@@ -307,4 +1017,93 @@ mod tests {
 
         assert_eq!(episode.total_reward, actual_reward);
     }
+
+    #[test]
+    fn estimated_transition_model() {
+        let grid = GridWorld::corner(2, 3, 0.8).unwrap();
+        let model = estimate_transition_model(&grid, 2000, &mut StdRng::seed_from_u64(0));
+
+        for state in grid.states() {
+            for action in grid.actions() {
+                for next_state in grid.states() {
+                    let p = grid.transition_probability(state, action, next_state);
+                    let estimate = model[state.id()][action.id()][next_state.id()];
+                    assert!((p - estimate).abs() < 0.05);
+                }
+            }
+        }
+
+        // a seeded generator makes the estimate reproducible
+        let estimate =
+            |seed| estimate_transition_model(&grid, 10, &mut StdRng::seed_from_u64(seed));
+        assert_eq!(estimate(1), estimate(1));
+    }
+
+    #[test]
+    fn transformed_rewards() {
+        let grid = GridWorld::corner(4, 4, 0.8).unwrap();
+        let scaled = TransformedMDP::new(GridWorld::corner(4, 4, 0.8).unwrap(), |r| 2.0 * r);
+
+        let optimizer = ValueIteration::default().with_theta(1e-9);
+        let policy = optimizer.find_optimal_policy(&grid).unwrap();
+        let scaled_policy = optimizer.find_optimal_policy(&scaled).unwrap();
+
+        // the optimal policy is invariant to positive scaling of the rewards
+        for (state, scaled_state) in grid.states().iter().zip(scaled.states()) {
+            assert_eq!(
+                policy.select_action(state),
+                scaled_policy.select_action(scaled_state)
+            );
+        }
+    }
+
+    #[test]
+    fn transition_table() {
+        let grid = GridWorld::corner(3, 3, 1.0).unwrap();
+        let scaled = TransformedMDP::new(GridWorld::corner(3, 3, 1.0).unwrap(), |r| 2.0 * r);
+
+        // moving east from the first row always succeeds
+        let tile = &grid.states()[1];
+        assert_eq!(
+            grid.transition_table(tile, &Move::East),
+            vec![(2, 1.0, -1.0)]
+        );
+        // the default implementation collects the transitions of the wrapped grid
+        assert_eq!(
+            scaled.transition_table(tile, &Move::East),
+            vec![(2, 1.0, -2.0)]
+        );
+    }
+
+    #[test]
+    fn shaped_stochastic_rewards() {
+        // moving between the two tiles is rewarded around 2
+        let probabilities = vec![vec![vec![0.0, 1.0]; 4]; 2];
+        let distributions: RewardDistributions = (0..2)
+            .map(|_| {
+                (0..4)
+                    .map(|_| {
+                        vec![
+                            Box::new(-1.0) as Box<dyn RewardDistribution + Send + Sync>,
+                            Box::new(Normal::new(2.0, 0.5).unwrap()),
+                        ]
+                    })
+                    .collect()
+            })
+            .collect();
+        let grid =
+            GridWorld::with_reward_distributions(1, 2, probabilities, distributions, |_| false)
+                .unwrap();
+        let shaped = ShapedMDP::new(grid, |tile: &Tile| tile.y as f64);
+
+        // the model yields the shaped mean reward rather than a sample
+        let (first, second) = (&shaped.states()[0], &shaped.states()[1]);
+        let expected = 2.0 + shaped.discount_factor() * 1.0;
+        for _ in 0..10 {
+            assert_eq!(
+                shaped.transitions(first, &Move::East).collect::<Vec<_>>(),
+                vec![(second, 1.0, expected)]
+            );
+        }
+    }
 }
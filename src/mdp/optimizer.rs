@@ -1,7 +1,9 @@
-use crate::mdp::model::{Action, MDPError, State, MDP};
+use crate::mdp::model::{Action, MDPError, ShapedMDP, State, MDP};
 use crate::mdp::policy::Policy;
+use rand::seq::SliceRandom;
 use rand::Rng;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
 
 /// Represents any algorithm that searches for an optimal policy given a Markov Decision Process.
 pub trait Optimizer<'a, S: State, A: Action, M: MDP<S, A>> {
@@ -13,46 +15,163 @@ pub trait Optimizer<'a, S: State, A: Action, M: MDP<S, A>> {
     fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>>;
 }
 
+/// Represents the outcome of an optimizer, that is, a policy along with the state values
+/// it was derived from.
+#[derive(Debug)]
+pub struct OptimizationResult<'a, S: State, A: Action> {
+    /// The greedy policy with respect to the values.
+    pub policy: Policy<'a, S, A>,
+    /// The state values indexed by state ID.
+    pub values: Vec<f64>,
+}
+
+/// Reports on the convergence of an iterative optimizer, e.g., for tuning `theta` or for
+/// detecting that `max_iterations` is too small.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OptimizerReport {
+    /// The number of sweeps performed.
+    pub iterations: usize,
+    /// Whether the final delta is below `theta`.
+    pub converged: bool,
+    /// The maximum change of any state value in the last sweep.
+    pub final_delta: f64,
+}
+
+/// Policy iteration alternates between evaluating the current policy and improving it greedily,
+/// until the policy is stable. By default, `theta` is `1e-6`, `max_iterations` is `10000` and
+/// the discount factor of the MDP is used.
 pub struct PolicyIteration {
     /// Small positive number determining the accuracy of estimation.
     pub theta: f64,
     // Maximum iterations for policy evaluation.
     pub max_iterations: usize,
+    /// Discount factor used in the backups instead of the discount factor of the MDP.
+    pub discount_override: Option<f64>,
 }
 
-impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for PolicyIteration {
-    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
-        let mut delta;
-        let mut values = vec![0.0; mdp.n_states()];
+impl Default for PolicyIteration {
+    fn default() -> Self {
+        PolicyIteration {
+            theta: 1e-6,
+            max_iterations: 10000,
+            discount_override: None,
+        }
+    }
+}
+
+impl PolicyIteration {
+    /// Sets the accuracy of the policy evaluation.
+    ///
+    /// # Arguments
+    ///
+    /// - `theta` - a small positive number.
+    pub fn with_theta(self, theta: f64) -> Self {
+        if theta <= 0.0 {
+            panic!("Invalid theta value: {theta}");
+        }
+
+        Self { theta, ..self }
+    }
+
+    /// Sets the maximum number of sweeps of each policy evaluation.
+    ///
+    /// # Arguments
+    ///
+    /// - `max_iterations` - the maximum number of sweeps.
+    pub fn with_max_iterations(self, max_iterations: usize) -> Self {
+        Self {
+            max_iterations,
+            ..self
+        }
+    }
+
+    /// Sets the discount factor used in the backups instead of the discount factor of the MDP.
+    ///
+    /// # Arguments
+    ///
+    /// - `gamma` - the discount factor, in (0, 1].
+    pub fn with_discount_override(self, gamma: f64) -> Self {
+        if gamma <= 0.0 || gamma > 1.0 {
+            panic!("Invalid gamma value: {gamma}");
+        }
+
+        Self {
+            discount_override: Some(gamma),
+            ..self
+        }
+    }
+
+    /// Returns the optimal policy along with a report on the convergence of the policy
+    /// evaluation. The iterations are the sweeps of all policy evaluations, while the final
+    /// delta is the one of the last policy evaluation.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    pub fn find_optimal_policy_with_report<'a, S: State, A: Action, M: MDP<S, A>>(
+        &self,
+        mdp: &'a M,
+    ) -> Result<(Policy<'a, S, A>, OptimizerReport), MDPError<'a, S>> {
+        self.find_optimal_policy_with_rng(mdp, &mut rand::thread_rng())
+    }
+
+    /// Returns the optimal policy along with a report on the convergence of the policy
+    /// evaluation exactly as [`PolicyIteration::find_optimal_policy_with_report`], but draws
+    /// the random initial policy from the given random number generator, e.g., a seeded one
+    /// for reproducible runs.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    /// - `rng` - the random number generator used to create the initial policy.
+    pub fn find_optimal_policy_with_rng<'a, S, A, M, R>(
+        &self,
+        mdp: &'a M,
+        rng: &mut R,
+    ) -> Result<(Policy<'a, S, A>, OptimizerReport), MDPError<'a, S>>
+    where
+        S: State,
+        A: Action,
+        M: MDP<S, A>,
+        R: Rng + ?Sized,
+    {
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
 
         // start from a random policy
-        let mut rng = rand::thread_rng();
-        let mut mapping: HashMap<&S, &A> = mdp
-            .states()
-            .iter()
-            .map(|state| (state, &mdp.actions()[rng.gen_range(0..mdp.n_actions())]))
-            .collect();
+        let initial_policy = Policy::random_with_rng(mdp.states(), mdp.actions(), rng);
+        self.find_optimal_policy_from(mdp, initial_policy)
+    }
+
+    /// Returns the optimal policy along with a report on the convergence of the policy
+    /// evaluation, starting from the given policy instead of a random one, e.g., a policy
+    /// created by [`Policy::random_with_rng`] for reproducible runs, or the solution of a similar
+    /// MDP, which typically requires far fewer iterations. It returns [`MDPError::NoAction`] if
+    /// the initial policy does not assign an action to some state.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    /// - `initial_policy` - the policy that is evaluated and improved first.
+    pub fn find_optimal_policy_from<'a, S: State, A: Action, M: MDP<S, A>>(
+        &self,
+        mdp: &'a M,
+        initial_policy: Policy<'a, S, A>,
+    ) -> Result<(Policy<'a, S, A>, OptimizerReport), MDPError<'a, S>> {
+        let discount = discount_factor(self.discount_override, mdp);
+        let mut delta = f64::NAN;
+        let mut iterations = 0;
+        let mut values = vec![0.0; mdp.n_states()];
+        let mut mapping = initial_policy.into_mapping();
 
         loop {
             // policy evaluation
             for _ in 0..self.max_iterations {
-                delta = 0f64;
-                for state in mdp.states() {
-                    let value = values[state.id()];
-                    match mapping.get(state) {
-                        Some(&action) => {
-                            let new_value = mdp.states().iter().fold(0.0, |v, next_state| {
-                                let r = mdp.reward(state, action, next_state);
-                                let p = mdp.transition_probability(state, action, next_state);
-                                v + p * (r + mdp.discount_factor() * values[next_state.id()])
-                            });
-
-                            delta = delta.max((value - new_value).abs());
-                            values[state.id()] = new_value;
-                        }
-                        None => return Err(MDPError::NoAction { state }),
-                    }
-                }
+                iterations += 1;
+                delta = evaluation_sweep(mdp, &mut values, discount, |state| {
+                    mapping.get(state).copied()
+                })?;
                 if delta < self.theta {
                     break;
                 }
@@ -66,12 +185,8 @@ impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for PolicyIte
                         let mut best_action = prev_action;
                         let mut best_value = f64::NEG_INFINITY;
 
-                        for action in mdp.actions() {
-                            let v = mdp.states().iter().fold(0.0, |v, s| {
-                                let r = mdp.reward(state, action, s);
-                                let p = mdp.transition_probability(state, action, s);
-                                v + p * (r + mdp.discount_factor() * values[s.id()])
-                            });
+                        for action in mdp.available_actions(state) {
+                            let v = q_value(mdp, state, action, &values, discount);
 
                             if v > best_value {
                                 best_value = v;
@@ -87,86 +202,1826 @@ impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for PolicyIte
             }
 
             if stable {
-                return Ok(Policy::new(mapping));
+                let report = OptimizerReport {
+                    iterations,
+                    converged: delta < self.theta,
+                    final_delta: delta,
+                };
+                return Ok((Policy::new(mapping), report));
+            }
+        }
+    }
+}
+
+impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for PolicyIteration {
+    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
+        self.find_optimal_policy_with_report(mdp)
+            .map(|(policy, _)| policy)
+    }
+}
+
+/// Modified policy iteration lies between value iteration and policy iteration. Each round
+/// improves the policy greedily with respect to the current values, and then evaluates it
+/// only partially, by a fixed number `eval_sweeps` of evaluation sweeps instead of until
+/// convergence. A single sweep approximates value iteration, while many sweeps approximate
+/// policy iteration. Few sweeps make each round cheap, but require more rounds, since every
+/// round performs a greedy maximization over all actions, whereas many sweeps waste work on
+/// evaluating policies that are about to change. The rounds stop once the Bellman residual,
+/// that is, the maximum change of any value under the greedy backup, is below `theta`.
+/// By default, `eval_sweeps` is `5`, `theta` is `1e-6` and `max_iterations` is `10000`.
+pub struct ModifiedPolicyIteration {
+    /// Number of policy evaluation sweeps per round.
+    pub eval_sweeps: usize,
+    /// Small positive number determining the accuracy of estimation.
+    pub theta: f64,
+    /// Maximum number of rounds.
+    pub max_iterations: usize,
+}
+
+impl Default for ModifiedPolicyIteration {
+    fn default() -> Self {
+        ModifiedPolicyIteration {
+            eval_sweeps: 5,
+            theta: 1e-6,
+            max_iterations: 10000,
+        }
+    }
+}
+
+impl ModifiedPolicyIteration {
+    /// Sets the number of policy evaluation sweeps per round.
+    ///
+    /// # Arguments
+    ///
+    /// - `eval_sweeps` - a positive number of sweeps.
+    pub fn with_eval_sweeps(self, eval_sweeps: usize) -> Self {
+        if eval_sweeps == 0 {
+            panic!("Invalid eval_sweeps value: {eval_sweeps}");
+        }
+
+        Self {
+            eval_sweeps,
+            ..self
+        }
+    }
+
+    /// Sets the accuracy of the state values.
+    ///
+    /// # Arguments
+    ///
+    /// - `theta` - a small positive number.
+    pub fn with_theta(self, theta: f64) -> Self {
+        if theta <= 0.0 {
+            panic!("Invalid theta value: {theta}");
+        }
+
+        Self { theta, ..self }
+    }
+
+    /// Sets the maximum number of rounds.
+    ///
+    /// # Arguments
+    ///
+    /// - `max_iterations` - the maximum number of rounds.
+    pub fn with_max_iterations(self, max_iterations: usize) -> Self {
+        Self {
+            max_iterations,
+            ..self
+        }
+    }
+
+    /// Returns the optimal policy along with a report on the convergence. The iterations are
+    /// the rounds of improvement and partial evaluation, while the final delta is the Bellman
+    /// residual of the last round.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    pub fn find_optimal_policy_with_report<'a, S: State, A: Action, M: MDP<S, A>>(
+        &self,
+        mdp: &'a M,
+    ) -> Result<(Policy<'a, S, A>, OptimizerReport), MDPError<'a, S>> {
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
+
+        let discount = mdp.discount_factor();
+        let mut values = vec![0.0; mdp.n_states()];
+        let mut actions = vec![&mdp.actions()[0]; mdp.n_states()];
+        let mut report = OptimizerReport {
+            iterations: 0,
+            converged: false,
+            final_delta: f64::NAN,
+        };
+
+        while report.iterations < self.max_iterations {
+            report.iterations += 1;
+
+            // policy improvement
+            let mut residual = 0f64;
+            for state in mdp.states() {
+                let (action, value) = greedy_action(mdp, state, &values, discount);
+                residual = residual.max((value - values[state.id()]).abs());
+                actions[state.id()] = action;
+            }
+            report.final_delta = residual;
+            if residual < self.theta {
+                report.converged = true;
+                break;
+            }
+
+            // partial policy evaluation
+            for _ in 0..self.eval_sweeps {
+                evaluation_sweep(mdp, &mut values, discount, |state| {
+                    Some(actions[state.id()])
+                })?;
             }
         }
+
+        let mapping = mdp.states().iter().zip(actions).collect();
+        Ok((Policy::new(mapping), report))
+    }
+}
+
+impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for ModifiedPolicyIteration {
+    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
+        self.find_optimal_policy_with_report(mdp)
+            .map(|(policy, _)| policy)
     }
 }
 
+/// A Bellman backup operator reducing the action values of a state into the state value.
+pub type Backup = Box<dyn Fn(&[f64]) -> f64>;
+
+/// Value iteration applies Bellman backups to all states, until the state values converge,
+/// and returns the greedy policy with respect to them. By default, `theta` is `1e-6`,
+/// `max_iterations` is `10000`, the maximum is used as backup, the discount factor of the MDP
+/// is used and the sweeps are in place.
 pub struct ValueIteration {
     /// Small positive number determining the accuracy of estimation.
     pub theta: f64,
     // Maximum iterations for policy evaluation.
     pub max_iterations: usize,
+    /// Custom backup operator applied to the values of the available actions of each state,
+    /// e.g., a soft maximum or a risk-averse operator. If none, the maximum is used, which
+    /// corresponds to the Bellman optimality backup.
+    pub backup: Option<Backup>,
+    /// Discount factor used in the backups instead of the discount factor of the MDP.
+    pub discount_override: Option<f64>,
+    /// If true, each sweep updates the values in place (Gauss-Seidel), so that backups of later
+    /// states use the values of earlier states already updated in the same sweep, which
+    /// typically converges in fewer sweeps. Otherwise, every backup of a sweep uses the values
+    /// of the previous sweep (Jacobi).
+    pub in_place: bool,
 }
 
-impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for ValueIteration {
-    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
-        let mut delta;
+impl Default for ValueIteration {
+    fn default() -> Self {
+        ValueIteration {
+            theta: 1e-6,
+            max_iterations: 10000,
+            backup: None,
+            discount_override: None,
+            in_place: true,
+        }
+    }
+}
+
+impl ValueIteration {
+    /// Sets the accuracy of the state values.
+    ///
+    /// # Arguments
+    ///
+    /// - `theta` - a small positive number.
+    pub fn with_theta(self, theta: f64) -> Self {
+        if theta <= 0.0 {
+            panic!("Invalid theta value: {theta}");
+        }
+
+        Self { theta, ..self }
+    }
+
+    /// Sets the maximum number of sweeps.
+    ///
+    /// # Arguments
+    ///
+    /// - `max_iterations` - the maximum number of sweeps.
+    pub fn with_max_iterations(self, max_iterations: usize) -> Self {
+        Self {
+            max_iterations,
+            ..self
+        }
+    }
+
+    /// Sets the backup operator applied to the values of the available actions of each state.
+    ///
+    /// # Arguments
+    ///
+    /// - `backup` - the backup operator.
+    pub fn with_backup(self, backup: Backup) -> Self {
+        Self {
+            backup: Some(backup),
+            ..self
+        }
+    }
+
+    /// Sets the discount factor used in the backups instead of the discount factor of the MDP.
+    ///
+    /// # Arguments
+    ///
+    /// - `gamma` - the discount factor, in (0, 1].
+    pub fn with_discount_override(self, gamma: f64) -> Self {
+        if gamma <= 0.0 || gamma > 1.0 {
+            panic!("Invalid gamma value: {gamma}");
+        }
+
+        Self {
+            discount_override: Some(gamma),
+            ..self
+        }
+    }
+
+    /// Sets whether the sweeps update the values in place (Gauss-Seidel) or use the values of
+    /// the previous sweep (Jacobi).
+    ///
+    /// # Arguments
+    ///
+    /// - `in_place` - true for in-place sweeps.
+    pub fn with_in_place(self, in_place: bool) -> Self {
+        Self { in_place, ..self }
+    }
+
+    /// Returns an upper bound on the number of sweeps value iteration needs to get within
+    /// `epsilon` of the optimal values, that is, `ln(epsilon (1 - gamma) / r_max) / ln(gamma)`.
+    /// The bound can be used to set `max_iterations` for discounted MDPs.
+    ///
+    /// # Arguments
+    ///
+    /// - `gamma` - the discount factor, strictly between 0 and 1.
+    /// - `epsilon` - the desired accuracy.
+    /// - `r_max` - the maximum absolute reward.
+    pub fn required_iterations(gamma: f64, epsilon: f64, r_max: f64) -> usize {
+        if gamma <= 0.0 || gamma >= 1.0 {
+            panic!("Invalid gamma value: {gamma}");
+        }
+        if epsilon <= 0.0 {
+            panic!("Invalid epsilon value: {epsilon}");
+        }
+        if r_max <= 0.0 {
+            panic!("Invalid maximum reward value: {r_max}");
+        }
+
+        let iterations = f64::ln(epsilon * (1.0 - gamma) / r_max) / f64::ln(gamma);
+        iterations.ceil().max(0.0) as usize
+    }
+
+    /// Returns the discount factor used in the backups.
+    fn discount_factor<S: State, A: Action, M: MDP<S, A>>(&self, mdp: &M) -> f64 {
+        discount_factor(self.discount_override, mdp)
+    }
+
+    /// Performs sweeps until the values converge, or the maximum number of iterations is
+    /// reached, and returns the values indexed by state ID.
+    fn solve<S: State, A: Action, M: MDP<S, A>>(&self, mdp: &M) -> Vec<f64> {
+        self.solve_with_report(mdp).0
+    }
+
+    /// Performs sweeps exactly as [`ValueIteration::solve`], but also reports on convergence.
+    fn solve_with_report<S: State, A: Action, M: MDP<S, A>>(
+        &self,
+        mdp: &M,
+    ) -> (Vec<f64>, OptimizerReport) {
+        let mut values = vec![0.0; mdp.n_states()];
+        let mut report = OptimizerReport {
+            iterations: 0,
+            converged: false,
+            final_delta: f64::NAN,
+        };
+        for _ in 0..self.max_iterations {
+            report.iterations += 1;
+            report.final_delta = self.sweep(mdp, &mut values);
+            if report.final_delta < self.theta {
+                report.converged = true;
+                break;
+            }
+        }
+        (values, report)
+    }
+
+    /// Returns the greedy policy with respect to the final values along with a report on the
+    /// convergence of the values.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    pub fn find_optimal_policy_with_report<'a, S: State, A: Action, M: MDP<S, A>>(
+        &self,
+        mdp: &'a M,
+    ) -> Result<(Policy<'a, S, A>, OptimizerReport), MDPError<'a, S>> {
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
+
+        let (values, report) = self.solve_with_report(mdp);
+        let policy = greedy_policy(mdp, &values, self.discount_factor(mdp));
+
+        Ok((policy, report))
+    }
+
+    /// Returns the greedy policy with respect to the converged values along with the values.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    pub fn find_optimal_policy_with_values<'a, S: State, A: Action, M: MDP<S, A>>(
+        &self,
+        mdp: &'a M,
+    ) -> Result<OptimizationResult<'a, S, A>, MDPError<'a, S>> {
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
+
+        let values = self.solve(mdp);
+
+        Ok(OptimizationResult {
+            policy: greedy_policy(mdp, &values, self.discount_factor(mdp)),
+            values,
+        })
+    }
+
+    /// Performs a single sweep of Bellman backups over all states, either in place or using
+    /// the values of the previous sweep, and returns the maximum change of any state value.
+    fn sweep<S: State, A: Action, M: MDP<S, A>>(&self, mdp: &M, values: &mut [f64]) -> f64 {
+        let discount = self.discount_factor(mdp);
+        let previous = if self.in_place {
+            None
+        } else {
+            Some(values.to_vec())
+        };
+        let mut delta = 0f64;
+        for state in mdp.states() {
+            let source = previous.as_deref().unwrap_or(values);
+            let value = match &self.backup {
+                Some(backup) => {
+                    let action_values: Vec<f64> = mdp
+                        .available_actions(state)
+                        .into_iter()
+                        .map(|action| q_value(mdp, state, action, source, discount))
+                        .collect();
+                    backup(&action_values)
+                }
+                None => greedy_action(mdp, state, source, discount).1,
+            };
+            delta = delta.max((value - values[state.id()]).abs());
+            values[state.id()] = value;
+        }
+        delta
+    }
+
+    /// Returns the empirical contraction rate of each sweep, that is, the ratio
+    /// `delta_{k+1} / delta_k` of the maximum value change between consecutive sweeps.
+    /// Since the Bellman optimality operator is a contraction, the rates should not
+    /// exceed the discount factor of the MDP.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    pub fn contraction_rates<S: State, A: Action, M: MDP<S, A>>(&self, mdp: &M) -> Vec<f64> {
         let mut values = vec![0.0; mdp.n_states()];
+        let mut rates = Vec::new();
+        let mut previous_delta = f64::NAN;
 
-        // policy evaluation
         for _ in 0..self.max_iterations {
-            delta = 0f64;
-            for state in mdp.states() {
-                let value = values[state.id()];
-
-                values[state.id()] =
-                    mdp.actions()
-                        .iter()
-                        .fold(f64::NEG_INFINITY, |max_v, action| {
-                            let x = mdp.states().iter().fold(0.0, |v, next_state| {
-                                let r = mdp.reward(state, action, next_state);
-                                let p = mdp.transition_probability(state, action, next_state);
-                                v + p * (r + mdp.discount_factor() * values[next_state.id()])
-                            });
-
-                            max_v.max(x)
-                        });
-
-                delta = delta.max((value - values[state.id()]).abs());
+            let delta = self.sweep(mdp, &mut values);
+            if previous_delta > 0.0 {
+                rates.push(delta / previous_delta);
             }
             if delta < self.theta {
                 break;
             }
+            previous_delta = delta;
+        }
+
+        rates
+    }
+}
+
+impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for ValueIteration {
+    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
+        self.find_optimal_policy_with_values(mdp)
+            .map(|result| result.policy)
+    }
+}
+
+/// Prioritized sweeping backs up states in the order of their Bellman error, instead of
+/// sweeping over all of them. Whenever the value of a state changes, the Bellman errors of its
+/// predecessors are recomputed and the predecessors having an error above `theta` are queued.
+/// Thus, computation is focused on the states whose values are still changing, which saves
+/// many backups on large MDPs where most states converge early. By default, `theta` is `1e-6`
+/// and `max_backups` is `1000000`.
+pub struct PrioritizedSweeping {
+    /// Small positive number determining the accuracy of estimation.
+    pub theta: f64,
+    /// Maximum number of single-state backups.
+    pub max_backups: usize,
+}
+
+impl Default for PrioritizedSweeping {
+    fn default() -> Self {
+        PrioritizedSweeping {
+            theta: 1e-6,
+            max_backups: 1000000,
+        }
+    }
+}
+
+impl PrioritizedSweeping {
+    /// Sets the Bellman error below which a state is not queued for a backup.
+    ///
+    /// # Arguments
+    ///
+    /// - `theta` - a small positive number.
+    pub fn with_theta(self, theta: f64) -> Self {
+        if theta <= 0.0 {
+            panic!("Invalid theta value: {theta}");
+        }
+
+        Self { theta, ..self }
+    }
+
+    /// Sets the maximum number of single-state backups.
+    ///
+    /// # Arguments
+    ///
+    /// - `max_backups` - the maximum number of backups.
+    pub fn with_max_backups(self, max_backups: usize) -> Self {
+        Self {
+            max_backups,
+            ..self
+        }
+    }
+
+    /// Backs up the states in the order of their Bellman error until no error exceeds `theta`,
+    /// or the backup budget is exhausted, and returns the values indexed by state ID along with
+    /// a report, where `iterations` counts single-state backups instead of sweeps and
+    /// `final_delta` is the largest Bellman error still queued.
+    fn solve_with_report<S: State, A: Action, M: MDP<S, A>>(
+        &self,
+        mdp: &M,
+    ) -> (Vec<f64>, OptimizerReport) {
+        let discount = mdp.discount_factor();
+        let bellman_error = |state: &S, values: &[f64]| {
+            (greedy_action(mdp, state, values, discount).1 - values[state.id()]).abs()
+        };
+
+        let mut predecessors = vec![Vec::new(); mdp.n_states()];
+        for state in mdp.states() {
+            for action in mdp.available_actions(state) {
+                for (next_state, p, _) in mdp.transitions(state, action) {
+                    if p > 0.0 && !predecessors[next_state.id()].contains(&state.id()) {
+                        predecessors[next_state.id()].push(state.id());
+                    }
+                }
+            }
         }
 
-        // output a policy
-        let mut mapping = HashMap::with_capacity(mdp.n_states());
+        let mut values = vec![0.0; mdp.n_states()];
+        let mut priorities = vec![0.0; mdp.n_states()];
+        let mut queue = BinaryHeap::new();
         for state in mdp.states() {
-            let mut best_action = &mdp.actions()[0];
-            let mut best_value = f64::NEG_INFINITY;
-
-            // find best action
-            for action in mdp.actions() {
-                let v = mdp.states().iter().fold(0.0, |v, s| {
-                    let r = mdp.reward(state, action, s);
-                    let p = mdp.transition_probability(state, action, s);
-                    v + p * (r + mdp.discount_factor() * values[s.id()])
+            let error = bellman_error(state, &values);
+            if error > self.theta {
+                priorities[state.id()] = error;
+                queue.push(PrioritizedState {
+                    priority: error,
+                    id: state.id(),
                 });
+            }
+        }
 
-                if v > best_value {
-                    best_value = v;
-                    best_action = action;
-                }
+        let mut backups = 0;
+        while backups < self.max_backups {
+            let Some(PrioritizedState { priority, id }) = queue.pop() else {
+                break;
+            };
+            // skip the stale entries of states that were queued again or already backed up
+            if priority != priorities[id] {
+                continue;
             }
 
-            mapping.insert(state, best_action);
+            let state = &mdp.states()[id];
+            values[id] = greedy_action(mdp, state, &values, discount).1;
+            priorities[id] = 0.0;
+            backups += 1;
+
+            for &predecessor in &predecessors[id] {
+                let error = bellman_error(&mdp.states()[predecessor], &values);
+                if error > self.theta && error != priorities[predecessor] {
+                    priorities[predecessor] = error;
+                    queue.push(PrioritizedState {
+                        priority: error,
+                        id: predecessor,
+                    });
+                }
+            }
         }
 
-        Ok(Policy::new(mapping))
+        let final_delta = priorities.iter().copied().fold(0.0, f64::max);
+        let report = OptimizerReport {
+            iterations: backups,
+            converged: final_delta <= self.theta,
+            final_delta,
+        };
+        (values, report)
     }
-}
 
-#[cfg(test)]
-mod tests {
-
-    use crate::mdp::environment::{GridWorld, Move};
-    use crate::mdp::model::{State, MDP};
-    use crate::mdp::optimizer::{Optimizer, PolicyIteration, ValueIteration};
+    /// Returns the greedy policy with respect to the final values along with a report on the
+    /// convergence of the values, where `iterations` counts single-state backups.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    pub fn find_optimal_policy_with_report<'a, S: State, A: Action, M: MDP<S, A>>(
+        &self,
+        mdp: &'a M,
+    ) -> Result<(Policy<'a, S, A>, OptimizerReport), MDPError<'a, S>> {
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
+
+        let (values, report) = self.solve_with_report(mdp);
+        let policy = greedy_policy(mdp, &values, mdp.discount_factor());
+
+        Ok((policy, report))
+    }
+
+    /// Returns the greedy policy with respect to the converged values along with the values.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    pub fn find_optimal_policy_with_values<'a, S: State, A: Action, M: MDP<S, A>>(
+        &self,
+        mdp: &'a M,
+    ) -> Result<OptimizationResult<'a, S, A>, MDPError<'a, S>> {
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
+
+        let values = self.solve_with_report(mdp).0;
+
+        Ok(OptimizationResult {
+            policy: greedy_policy(mdp, &values, mdp.discount_factor()),
+            values,
+        })
+    }
+}
+
+impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for PrioritizedSweeping {
+    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
+        self.find_optimal_policy_with_values(mdp)
+            .map(|result| result.policy)
+    }
+}
+
+/// A state in the queue of prioritized sweeping, ordered such that the state having the
+/// highest Bellman error is popped first from a max-heap.
+#[derive(Debug, PartialEq)]
+struct PrioritizedState {
+    priority: f64,
+    id: usize,
+}
+
+impl Eq for PrioritizedState {}
+
+impl Ord for PrioritizedState {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .total_cmp(&other.priority)
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+impl PartialOrd for PrioritizedState {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Linear programming computes the optimal state values directly, as the solution of
+/// `minimize Σ_s v(s)` subject to `v(s) ≥ Σ_s' p(s'|s,a) [r(s,a,s') + γ v(s')]` for every
+/// state `s` and available action `a`. The smallest values satisfying all constraints are
+/// the fixed point of the Bellman optimality operator, and the optimal policy is greedy with
+/// respect to them. Terminal states, as well as states having no available action, are fixed
+/// to zero value.
+///
+/// The solver is exact, but builds a constraint for every state-action pair, and thus it is
+/// mostly useful for validating the iterative optimizers on small MDPs.
+#[cfg(feature = "lp")]
+#[derive(Default)]
+pub struct LinearProgramming;
+
+#[cfg(feature = "lp")]
+impl LinearProgramming {
+    /// Returns an optimal policy for the provided MDP along with the optimal state values.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    pub fn find_optimal_policy_with_values<'a, S: State, A: Action, M: MDP<S, A>>(
+        &self,
+        mdp: &'a M,
+    ) -> Result<OptimizationResult<'a, S, A>, MDPError<'a, S>> {
+        use minilp::{ComparisonOp, OptimizationDirection, Problem};
+
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
+
+        let discount = mdp.discount_factor();
+        let mut problem = Problem::new(OptimizationDirection::Minimize);
+        let variables: Vec<_> = mdp
+            .states()
+            .iter()
+            .map(|state| {
+                if mdp.is_terminal(state) || mdp.available_actions(state).is_empty() {
+                    problem.add_var(1.0, (0.0, 0.0))
+                } else {
+                    problem.add_var(1.0, (f64::NEG_INFINITY, f64::INFINITY))
+                }
+            })
+            .collect();
+
+        for state in mdp.states() {
+            if mdp.is_terminal(state) {
+                continue;
+            }
+            for action in mdp.available_actions(state) {
+                // v(s) - γ Σ_s' p(s'|s,a) v(s') ≥ Σ_s' p(s'|s,a) r(s,a,s')
+                let mut coefficients = vec![0.0; mdp.n_states()];
+                coefficients[state.id()] = 1.0;
+                let mut expected_reward = 0.0;
+                for (next_state, p, r) in mdp.transitions(state, action) {
+                    coefficients[next_state.id()] -= discount * p;
+                    expected_reward += p * r;
+                }
+                let expression: Vec<_> = coefficients
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, coefficient)| *coefficient != 0.0)
+                    .map(|(id, coefficient)| (variables[id], coefficient))
+                    .collect();
+                problem.add_constraint(expression, ComparisonOp::Ge, expected_reward);
+            }
+        }
+
+        let solution = problem.solve().map_err(|_| MDPError::Unsolvable)?;
+        let values: Vec<f64> = variables
+            .iter()
+            .map(|&variable| solution[variable])
+            .collect();
+
+        Ok(OptimizationResult {
+            policy: greedy_policy(mdp, &values, discount),
+            values,
+        })
+    }
+}
+
+#[cfg(feature = "lp")]
+impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for LinearProgramming {
+    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
+        self.find_optimal_policy_with_values(mdp)
+            .map(|result| result.policy)
+    }
+}
+
+/// Real-Time Dynamic Programming (RTDP) interleaves planning and acting. Starting from zero
+/// values, the agent repeatedly performs a Bellman backup on its current state and then
+/// takes a real transition following a greedy policy with ε-exploration. In contrast to
+/// value iteration, computation is focused on the states that are actually visited from
+/// the starting state, so large parts of the state space may never be backed up.
+pub struct RealTimeDynamicProgramming {
+    /// The ID of the state every trial starts from.
+    pub starting_state: usize,
+    /// Probability of taking a random action instead of the greedy one.
+    pub epsilon: f64,
+    /// Number of trials, each starting from the starting state.
+    pub episodes: usize,
+    /// Maximum number of steps per trial, in case no terminal state is reached.
+    pub max_steps: usize,
+}
+
+impl RealTimeDynamicProgramming {
+    /// Returns the partial value function indexed by state ID. States that were never visited
+    /// keep their initial zero value.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    /// - `rng` - the random number generator used to select the actions and next states.
+    pub fn learn<'a, S: State, A: Action, M: MDP<S, A>, R: Rng + ?Sized>(
+        &self,
+        mdp: &'a M,
+        rng: &mut R,
+    ) -> Result<Vec<f64>, MDPError<'a, S>> {
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
+        if self.starting_state >= mdp.n_states() {
+            panic!("Invalid starting_state value: {}", self.starting_state);
+        }
+        if !(0.0..=1.0).contains(&self.epsilon) {
+            panic!("Invalid epsilon value: {}", self.epsilon);
+        }
+
+        let mut values = vec![0.0; mdp.n_states()];
+
+        for _ in 0..self.episodes {
+            let mut state = &mdp.states()[self.starting_state];
+
+            for _ in 0..self.max_steps {
+                if mdp.is_terminal(state) {
+                    break;
+                }
+
+                // back up the current state and find the greedy action
+                let (greedy_action, value) =
+                    greedy_action(mdp, state, &values, mdp.discount_factor());
+                values[state.id()] = value;
+
+                // act either randomly or greedily
+                let action = if rng.gen_bool(self.epsilon) {
+                    mdp.available_actions(state)
+                        .choose(rng)
+                        .copied()
+                        .unwrap_or(greedy_action)
+                } else {
+                    greedy_action
+                };
+                state = mdp.act_with(state, action, rng);
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Returns the greedy policy along with the partial value function indexed by state ID.
+    /// States that were never visited keep their initial zero value.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    pub fn find_optimal_policy_with_values<'a, S: State, A: Action, M: MDP<S, A>>(
+        &self,
+        mdp: &'a M,
+    ) -> Result<OptimizationResult<'a, S, A>, MDPError<'a, S>> {
+        let values = self.learn(mdp, &mut rand::thread_rng())?;
+
+        // output a greedy policy
+        Ok(OptimizationResult {
+            policy: Policy::greedy_from_values(mdp, &values),
+            values,
+        })
+    }
+}
+
+impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for RealTimeDynamicProgramming {
+    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
+        self.find_optimal_policy_with_values(mdp)
+            .map(|result| result.policy)
+    }
+}
+
+/// Q-learning is a model-free, off-policy temporal-difference control method. It learns the
+/// action values from sampled transitions, following an ε-greedy behavior policy, while each
+/// update bootstraps from the best action of the next state, that is,
+/// `Q(s,a) ← Q(s,a) + α (r + γ max_a' Q(s',a') - Q(s,a))`.
+pub struct QLearning {
+    /// Step size of the action value updates.
+    pub alpha: f64,
+    /// Probability of taking a random action instead of the greedy one.
+    pub epsilon: f64,
+    /// Number of episodes, each starting from a random state.
+    pub episodes: usize,
+    /// Maximum number of steps per episode, in case no terminal state is reached.
+    pub max_steps_per_episode: usize,
+}
+
+impl QLearning {
+    /// Returns the learned action values, indexed by state ID and then by action ID.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    /// - `rng` - the random number generator used to select the starting states, actions and
+    ///   next states.
+    pub fn learn<'a, S: State, A: Action, M: MDP<S, A>, R: Rng + ?Sized>(
+        &self,
+        mdp: &'a M,
+        rng: &mut R,
+    ) -> Result<Vec<Vec<f64>>, MDPError<'a, S>> {
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
+
+        let mut q_table = vec![vec![0.0; mdp.n_actions()]; mdp.n_states()];
+
+        for _ in 0..self.episodes {
+            let mut state = &mdp.states()[rng.gen_range(0..mdp.n_states())];
+
+            for _ in 0..self.max_steps_per_episode {
+                if mdp.is_terminal(state) {
+                    break;
+                }
+
+                let action = epsilon_greedy_action(mdp, state, &q_table, self.epsilon, rng);
+                let next_state = mdp.act_with(state, action, rng);
+
+                // bootstrap from the greedy action, unless the episode ends
+                let next_value = if mdp.is_terminal(next_state) {
+                    0.0
+                } else {
+                    q_table[next_state.id()][greedy_q_action(mdp, next_state, &q_table).id()]
+                };
+                let target =
+                    mdp.reward(state, action, next_state) + mdp.discount_factor() * next_value;
+                let q = &mut q_table[state.id()][action.id()];
+                *q += self.alpha * (target - *q);
+
+                state = next_state;
+            }
+        }
+
+        Ok(q_table)
+    }
+}
+
+impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for QLearning {
+    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
+        let q_table = self.learn(mdp, &mut rand::thread_rng())?;
+        Ok(greedy_q_policy(mdp, &q_table))
+    }
+}
+
+/// SARSA is a model-free, on-policy temporal-difference control method. In contrast to
+/// Q-learning, each update bootstraps from the action actually selected in the next state by
+/// the ε-greedy behavior policy, that is, `Q(s,a) ← Q(s,a) + α (r + γ Q(s',a') - Q(s,a))`.
+/// Thus, the learned values account for exploration, which leads to safer policies when
+/// exploratory actions are costly.
+pub struct Sarsa {
+    /// Step size of the action value updates.
+    pub alpha: f64,
+    /// Probability of taking a random action instead of the greedy one.
+    pub epsilon: f64,
+    /// Number of episodes, each starting from a random state.
+    pub episodes: usize,
+    /// Maximum number of steps per episode, in case no terminal state is reached.
+    pub max_steps: usize,
+}
+
+impl Sarsa {
+    /// Returns the learned action values, indexed by state ID and then by action ID.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    /// - `rng` - the random number generator used to select the starting states, actions and
+    ///   next states.
+    pub fn learn<'a, S: State, A: Action, M: MDP<S, A>, R: Rng + ?Sized>(
+        &self,
+        mdp: &'a M,
+        rng: &mut R,
+    ) -> Result<Vec<Vec<f64>>, MDPError<'a, S>> {
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
+
+        let mut q_table = vec![vec![0.0; mdp.n_actions()]; mdp.n_states()];
+
+        for _ in 0..self.episodes {
+            let mut state = &mdp.states()[rng.gen_range(0..mdp.n_states())];
+            let mut action = epsilon_greedy_action(mdp, state, &q_table, self.epsilon, rng);
+
+            for _ in 0..self.max_steps {
+                if mdp.is_terminal(state) {
+                    break;
+                }
+
+                let next_state = mdp.act_with(state, action, rng);
+                let next_action =
+                    epsilon_greedy_action(mdp, next_state, &q_table, self.epsilon, rng);
+
+                // bootstrap from the selected action, unless the episode ends
+                let next_value = if mdp.is_terminal(next_state) {
+                    0.0
+                } else {
+                    q_table[next_state.id()][next_action.id()]
+                };
+                let target =
+                    mdp.reward(state, action, next_state) + mdp.discount_factor() * next_value;
+                let q = &mut q_table[state.id()][action.id()];
+                *q += self.alpha * (target - *q);
+
+                state = next_state;
+                action = next_action;
+            }
+        }
+
+        Ok(q_table)
+    }
+}
+
+impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for Sarsa {
+    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
+        let q_table = self.learn(mdp, &mut rand::thread_rng())?;
+        Ok(greedy_q_policy(mdp, &q_table))
+    }
+}
+
+/// Expected SARSA is a model-free temporal-difference control method. Each update bootstraps
+/// from the expected action value of the next state under the ε-greedy policy, that is,
+/// `Q(s,a) ← Q(s,a) + α (r + γ Σ_a' π(a'|s') Q(s',a') - Q(s,a))`, instead of the value of a
+/// sampled next action. Thus, it learns the same values as SARSA, but with a lower variance.
+pub struct ExpectedSarsa {
+    /// Step size of the action value updates.
+    pub alpha: f64,
+    /// Probability of taking a random action instead of the greedy one.
+    pub epsilon: f64,
+    /// Number of episodes, each starting from a random state.
+    pub episodes: usize,
+    /// Maximum number of steps per episode, in case no terminal state is reached.
+    pub max_steps: usize,
+}
+
+impl ExpectedSarsa {
+    /// Returns the learned action values, indexed by state ID and then by action ID.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    /// - `rng` - the random number generator used to select the starting states, actions and
+    ///   next states.
+    pub fn learn<'a, S: State, A: Action, M: MDP<S, A>, R: Rng + ?Sized>(
+        &self,
+        mdp: &'a M,
+        rng: &mut R,
+    ) -> Result<Vec<Vec<f64>>, MDPError<'a, S>> {
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
+
+        let mut q_table = vec![vec![0.0; mdp.n_actions()]; mdp.n_states()];
+
+        for _ in 0..self.episodes {
+            let mut state = &mdp.states()[rng.gen_range(0..mdp.n_states())];
+
+            for _ in 0..self.max_steps {
+                if mdp.is_terminal(state) {
+                    break;
+                }
+
+                let action = epsilon_greedy_action(mdp, state, &q_table, self.epsilon, rng);
+                let next_state = mdp.act_with(state, action, rng);
+
+                // bootstrap from the expected value under the ε-greedy policy
+                let next_value = if mdp.is_terminal(next_state) {
+                    0.0
+                } else {
+                    epsilon_greedy_probabilities(mdp, next_state, &q_table, self.epsilon)
+                        .into_iter()
+                        .map(|(action, p)| p * q_table[next_state.id()][action.id()])
+                        .sum()
+                };
+                let target =
+                    mdp.reward(state, action, next_state) + mdp.discount_factor() * next_value;
+                let q = &mut q_table[state.id()][action.id()];
+                *q += self.alpha * (target - *q);
+
+                state = next_state;
+            }
+        }
+
+        Ok(q_table)
+    }
+}
+
+impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for ExpectedSarsa {
+    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
+        let q_table = self.learn(mdp, &mut rand::thread_rng())?;
+        Ok(greedy_q_policy(mdp, &q_table))
+    }
+}
+
+/// n-step SARSA bootstraps from the action value `n` steps ahead instead of the next one, that
+/// is, the target of each update is `r_1 + γ r_2 + ... + γ^(n-1) r_n + γ^n Q(s_n,a_n)`, so
+/// rewards propagate `n` states back per update. For `n = 1` it is identical to SARSA, while
+/// as `n` grows it approaches Monte Carlo control.
+pub struct NStepSarsa {
+    /// Number of rewards accumulated before bootstrapping.
+    pub n: usize,
+    /// Step size of the action value updates.
+    pub alpha: f64,
+    /// Probability of taking a random action instead of the greedy one.
+    pub epsilon: f64,
+    /// Number of episodes, each starting from a random state.
+    pub episodes: usize,
+    /// Maximum number of steps per episode, in case no terminal state is reached.
+    pub max_steps: usize,
+}
+
+impl NStepSarsa {
+    /// Returns the learned action values, indexed by state ID and then by action ID.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    /// - `rng` - the random number generator used to select the starting states, actions and
+    ///   next states.
+    pub fn learn<'a, S: State, A: Action, M: MDP<S, A>, R: Rng + ?Sized>(
+        &self,
+        mdp: &'a M,
+        rng: &mut R,
+    ) -> Result<Vec<Vec<f64>>, MDPError<'a, S>> {
+        if self.n == 0 {
+            panic!("Invalid n value: {}", self.n);
+        }
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
+
+        let discount = mdp.discount_factor();
+        let mut q_table = vec![vec![0.0; mdp.n_actions()]; mdp.n_states()];
+
+        for _ in 0..self.episodes {
+            let mut state = &mdp.states()[rng.gen_range(0..mdp.n_states())];
+            let mut action = epsilon_greedy_action(mdp, state, &q_table, self.epsilon, rng);
+            // the last n state-action pairs, along with the rewards that followed them
+            let mut window = VecDeque::with_capacity(self.n);
+
+            for _ in 0..self.max_steps {
+                if mdp.is_terminal(state) {
+                    break;
+                }
+
+                let next_state = mdp.act_with(state, action, rng);
+                let next_action =
+                    epsilon_greedy_action(mdp, next_state, &q_table, self.epsilon, rng);
+                window.push_back((state, action, mdp.reward(state, action, next_state)));
+
+                if window.len() == self.n {
+                    let next_value = if mdp.is_terminal(next_state) {
+                        0.0
+                    } else {
+                        q_table[next_state.id()][next_action.id()]
+                    };
+                    self.update(&mut q_table, &mut window, next_value, discount);
+                }
+
+                state = next_state;
+                action = next_action;
+            }
+
+            // the remaining pairs see fewer than n rewards, since the episode ended
+            let last_value = if mdp.is_terminal(state) {
+                0.0
+            } else {
+                q_table[state.id()][action.id()]
+            };
+            while !window.is_empty() {
+                self.update(&mut q_table, &mut window, last_value, discount);
+            }
+        }
+
+        Ok(q_table)
+    }
+
+    /// Updates the value of the oldest state-action pair of the window towards the discounted
+    /// rewards of the window followed by the given bootstrap value, and removes the pair.
+    fn update<S: State, A: Action>(
+        &self,
+        q_table: &mut [Vec<f64>],
+        window: &mut VecDeque<(&S, &A, f64)>,
+        bootstrap: f64,
+        discount: f64,
+    ) {
+        let target = window
+            .iter()
+            .rev()
+            .fold(bootstrap, |value, (_, _, reward)| reward + discount * value);
+        if let Some((state, action, _)) = window.pop_front() {
+            let q = &mut q_table[state.id()][action.id()];
+            *q += self.alpha * (target - *q);
+        }
+    }
+}
+
+impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for NStepSarsa {
+    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
+        let q_table = self.learn(mdp, &mut rand::thread_rng())?;
+        Ok(greedy_q_policy(mdp, &q_table))
+    }
+}
+
+/// Monte Carlo control learns the action values from complete episodes, following an
+/// ε-greedy behavior policy. In contrast to temporal-difference methods, it does not
+/// bootstrap, that is, the value of each state-action pair is the average of the discounted
+/// returns following its first visit in each episode.
+pub struct MonteCarloControl {
+    /// Probability of taking a random action instead of the greedy one.
+    pub epsilon: f64,
+    /// Number of episodes, each starting from a random state.
+    pub episodes: usize,
+    /// Maximum number of steps per episode, in case no terminal state is reached.
+    pub max_steps: usize,
+}
+
+impl MonteCarloControl {
+    /// Returns the learned action values, indexed by state ID and then by action ID.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    /// - `rng` - the random number generator used to select the starting states, actions and
+    ///   next states.
+    pub fn learn<'a, S: State, A: Action, M: MDP<S, A>, R: Rng + ?Sized>(
+        &self,
+        mdp: &'a M,
+        rng: &mut R,
+    ) -> Result<Vec<Vec<f64>>, MDPError<'a, S>> {
+        if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+            return Err(MDPError::Empty);
+        }
+
+        let mut q_table = vec![vec![0.0; mdp.n_actions()]; mdp.n_states()];
+        let mut visits = vec![vec![0usize; mdp.n_actions()]; mdp.n_states()];
+
+        for _ in 0..self.episodes {
+            // generate an episode following the ε-greedy policy
+            let mut state = &mdp.states()[rng.gen_range(0..mdp.n_states())];
+            let mut steps = Vec::new();
+            for _ in 0..self.max_steps {
+                if mdp.is_terminal(state) {
+                    break;
+                }
+
+                let action = epsilon_greedy_action(mdp, state, &q_table, self.epsilon, rng);
+                let next_state = mdp.act_with(state, action, rng);
+                steps.push((
+                    state.id(),
+                    action.id(),
+                    mdp.reward(state, action, next_state),
+                ));
+                state = next_state;
+            }
+
+            // the returns of each step, accumulated backwards
+            let mut returns = vec![0.0; steps.len()];
+            let mut g = 0.0;
+            for (t, &(_, _, reward)) in steps.iter().enumerate().rev() {
+                g = reward + mdp.discount_factor() * g;
+                returns[t] = g;
+            }
+
+            // average the return following the first visit of each state-action pair
+            let mut visited = vec![vec![false; mdp.n_actions()]; mdp.n_states()];
+            for (&(s, a, _), g) in steps.iter().zip(returns) {
+                if !visited[s][a] {
+                    visited[s][a] = true;
+                    visits[s][a] += 1;
+                    q_table[s][a] += (g - q_table[s][a]) / visits[s][a] as f64;
+                }
+            }
+        }
+
+        Ok(q_table)
+    }
+}
+
+impl<'a, S: State, A: Action, M: MDP<S, A>> Optimizer<'a, S, A, M> for MonteCarloControl {
+    fn find_optimal_policy(&self, mdp: &'a M) -> Result<Policy<'a, S, A>, MDPError<'a, S>> {
+        let q_table = self.learn(mdp, &mut rand::thread_rng())?;
+        Ok(greedy_q_policy(mdp, &q_table))
+    }
+}
+
+/// TD(λ) evaluates a fixed policy from sampled experience, using eligibility traces to
+/// propagate each temporal-difference error to recently visited states. A λ of zero gives
+/// one-step TD, while a λ of one approaches Monte Carlo evaluation.
+pub struct TdLambda {
+    /// Trace decay parameter, between 0 and 1.
+    pub lambda: f64,
+    /// Step size of the value updates.
+    pub alpha: f64,
+    /// Number of episodes, each starting from a random state.
+    pub episodes: usize,
+    /// Maximum number of steps per episode, in case no terminal state is reached.
+    pub max_steps: usize,
+}
+
+impl TdLambda {
+    /// Evaluates the given policy and returns the estimated values indexed by state ID,
+    /// along with the root-mean-square change of the values in each episode. The changes
+    /// indicate whether the estimation converges or diverges, e.g., due to a large step size.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - Markov Decision Process.
+    /// - `policy` - the policy to be evaluated.
+    /// - `rng` - the random number generator used to select the starting and next states.
+    pub fn evaluate<'a, S: State, A: Action, M: MDP<S, A>, R: Rng + ?Sized>(
+        &self,
+        mdp: &'a M,
+        policy: &Policy<S, A>,
+        rng: &mut R,
+    ) -> Result<(Vec<f64>, Vec<f64>), MDPError<'a, S>> {
+        if mdp.n_states() == 0 {
+            return Err(MDPError::Empty);
+        }
+
+        let mut values = vec![0.0; mdp.n_states()];
+        let mut changes = Vec::with_capacity(self.episodes);
+
+        for _ in 0..self.episodes {
+            let previous_values = values.clone();
+            let mut traces = vec![0.0; mdp.n_states()];
+            let mut state = &mdp.states()[rng.gen_range(0..mdp.n_states())];
+
+            for _ in 0..self.max_steps {
+                if mdp.is_terminal(state) {
+                    break;
+                }
+
+                let action = match policy.select_action(state) {
+                    Some(action) => action,
+                    None => return Err(MDPError::NoAction { state }),
+                };
+                let next_state = mdp.act_with(state, action, rng);
+
+                // temporal-difference error of the transition
+                let td_error = mdp.reward(state, action, next_state)
+                    + mdp.discount_factor() * values[next_state.id()]
+                    - values[state.id()];
+
+                // accumulate the trace of the current state and update all values
+                traces[state.id()] += 1.0;
+                for (value, trace) in values.iter_mut().zip(traces.iter_mut()) {
+                    *value += self.alpha * td_error * *trace;
+                    *trace *= mdp.discount_factor() * self.lambda;
+                }
+
+                state = next_state;
+            }
+
+            let squared_change = values
+                .iter()
+                .zip(previous_values)
+                .map(|(v, previous)| (v - previous).powi(2))
+                .sum::<f64>();
+            changes.push(f64::sqrt(squared_change / mdp.n_states() as f64));
+        }
+
+        Ok((values, changes))
+    }
+}
+
+/// Returns, for each state, the number of available actions whose value is within `tolerance`
+/// of the best action value, according to the given state values. States having a single
+/// optimal action constrain the optimal policy, while ties indicate that a deterministic
+/// policy represents only one of several optimal solutions. The counts are indexed by state ID.
+///
+/// # Arguments
+///
+/// - `mdp` - Markov Decision Process.
+/// - `values` - the state values indexed by state ID, e.g., the optimal values.
+/// - `tolerance` - the maximum difference from the best action value to count as optimal.
+pub fn optimal_action_counts<S: State, A: Action, M: MDP<S, A>>(
+    mdp: &M,
+    values: &[f64],
+    tolerance: f64,
+) -> Vec<usize> {
+    let mut counts = vec![0; mdp.n_states()];
+
+    for state in mdp.states() {
+        let action_values: Vec<f64> = mdp
+            .available_actions(state)
+            .into_iter()
+            .map(|action| q_value(mdp, state, action, values, mdp.discount_factor()))
+            .collect();
+        let best_value = action_values
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        counts[state.id()] = action_values
+            .iter()
+            .filter(|&&v| best_value - v <= tolerance)
+            .count();
+    }
+
+    counts
+}
+
+/// Returns the stationary distribution of the Markov chain induced by a policy, indexed by
+/// state ID. The distribution is approximated by power iteration, starting from the uniform
+/// distribution over states. Note that in episodic MDPs the distribution concentrates on
+/// the terminal states.
+///
+/// # Arguments
+///
+/// - `mdp` - Markov Decision Process.
+/// - `policy` - the policy inducing the Markov chain.
+/// - `iterations` - the number of power iterations.
+pub fn stationary_distribution<'a, S: State, A: Action, M: MDP<S, A>>(
+    mdp: &'a M,
+    policy: &Policy<S, A>,
+    iterations: usize,
+) -> Result<Vec<f64>, MDPError<'a, S>> {
+    if mdp.n_states() == 0 {
+        return Err(MDPError::Empty);
+    }
+
+    let mut distribution = vec![1.0 / mdp.n_states() as f64; mdp.n_states()];
+    for _ in 0..iterations {
+        let mut next_distribution = vec![0.0; mdp.n_states()];
+        for state in mdp.states() {
+            let action = match policy.select_action(state) {
+                Some(action) => action,
+                None => return Err(MDPError::NoAction { state }),
+            };
+            for (next_state, p, _) in mdp.transitions(state, action) {
+                next_distribution[next_state.id()] += distribution[state.id()] * p;
+            }
+        }
+        distribution = next_distribution;
+    }
+
+    Ok(distribution)
+}
+
+/// Returns the long-run average reward per step of a policy, that is, the expected reward
+/// under the stationary distribution of the policy, `Σ_s d(s) Σ_s' P(s'|s,π(s)) R(s,π(s),s')`.
+/// The average reward is the objective of undiscounted continuing tasks.
+///
+/// # Arguments
+///
+/// - `mdp` - Markov Decision Process.
+/// - `policy` - the policy of interest.
+/// - `iterations` - the number of power iterations approximating the stationary distribution.
+pub fn average_reward_rate<'a, S: State, A: Action, M: MDP<S, A>>(
+    mdp: &'a M,
+    policy: &Policy<S, A>,
+    iterations: usize,
+) -> Result<f64, MDPError<'a, S>> {
+    let distribution = stationary_distribution(mdp, policy, iterations)?;
+
+    let mut rate = 0.0;
+    for state in mdp.states() {
+        let action = match policy.select_action(state) {
+            Some(action) => action,
+            None => return Err(MDPError::NoAction { state }),
+        };
+        let expected_reward = mdp
+            .transitions(state, action)
+            .fold(0.0, |expected, (_, p, r)| expected + p * r);
+        rate += distribution[state.id()] * expected_reward;
+    }
+
+    Ok(rate)
+}
+
+/// Solves the MDP by value iteration once for each of the given discount factors and returns
+/// the resulting value functions, indexed by state ID, paired with their discount factor.
+/// The sweep shows how the discount shapes the solution, from myopic to farsighted.
+///
+/// # Arguments
+///
+/// - `mdp` - Markov Decision Process.
+/// - `gammas` - the discount factors, each in (0, 1].
+/// - `theta` - small positive number determining the accuracy of estimation.
+/// - `max_iterations` - maximum iterations of value iteration per discount factor.
+pub fn discount_sweep<S: State, A: Action, M: MDP<S, A>>(
+    mdp: &M,
+    gammas: &[f64],
+    theta: f64,
+    max_iterations: usize,
+) -> Vec<(f64, Vec<f64>)> {
+    gammas
+        .iter()
+        .map(|&gamma| {
+            let optimizer = ValueIteration::default()
+                .with_theta(theta)
+                .with_max_iterations(max_iterations)
+                .with_discount_override(gamma);
+            (gamma, optimizer.solve(mdp))
+        })
+        .collect()
+}
+
+/// Solves both an MDP and its potential-based shaping by value iteration and returns true if
+/// the extracted policies agree on every state. Since optimal policies need not be unique,
+/// two actions agree if they have the same value in the inner MDP, up to a small tolerance.
+/// Potential-based shaping should never change the optimal policies, so a false result
+/// indicates a misconfigured shaping, e.g., one that is not potential-based.
+///
+/// # Arguments
+///
+/// - `base` - the inner MDP.
+/// - `shaped` - the shaped MDP.
+/// - `theta` - small positive number determining the accuracy of estimation.
+/// - `max_iterations` - maximum iterations of value iteration.
+pub fn verify_shaping_invariance<S, A, M, P>(
+    base: &M,
+    shaped: &ShapedMDP<M, P>,
+    theta: f64,
+    max_iterations: usize,
+) -> bool
+where
+    S: State,
+    A: Action,
+    M: MDP<S, A>,
+    P: Fn(&S) -> f64,
+{
+    // the tolerance within which two action values are considered equal
+    const TOLERANCE: f64 = 1e-6;
+
+    let optimizer = ValueIteration::default()
+        .with_theta(theta)
+        .with_max_iterations(max_iterations);
+    let (base_result, shaped_result) = match (
+        optimizer.find_optimal_policy_with_values(base),
+        optimizer.find_optimal_policy_with_values(shaped),
+    ) {
+        (Ok(base_result), Ok(shaped_result)) => (base_result, shaped_result),
+        _ => return false,
+    };
+
+    base.states().iter().all(|state| {
+        match (
+            base_result.policy.select_action(state),
+            shaped_result.policy.select_action(state),
+        ) {
+            (Some(base_action), Some(shaped_action)) => {
+                let discount = base.discount_factor();
+                let base_value = q_value(base, state, base_action, &base_result.values, discount);
+                let shaped_value =
+                    q_value(base, state, shaped_action, &base_result.values, discount);
+                (base_value - shaped_value).abs() <= TOLERANCE
+            }
+            _ => false,
+        }
+    })
+}
+
+/// Returns the values of a policy, indexed by state ID, by iterative policy evaluation.
+///
+/// # Arguments
+///
+/// - `mdp` - Markov Decision Process.
+/// - `policy` - the policy of interest.
+/// - `theta` - small positive number determining the accuracy of estimation.
+/// - `max_iterations` - maximum iterations of policy evaluation.
+pub fn evaluate_policy<'a, S: State, A: Action, M: MDP<S, A>>(
+    mdp: &'a M,
+    policy: &Policy<S, A>,
+    theta: f64,
+    max_iterations: usize,
+) -> Result<Vec<f64>, MDPError<'a, S>> {
+    let mut values = vec![0.0; mdp.n_states()];
+
+    for _ in 0..max_iterations {
+        let delta = evaluation_sweep(mdp, &mut values, mdp.discount_factor(), |state| {
+            policy.select_action(state)
+        })?;
+        if delta < theta {
+            break;
+        }
+    }
+
+    Ok(values)
+}
+
+/// Performs a single in-place sweep of policy evaluation over all states, where the action
+/// of each state is given by `select_action`, and returns the maximum change of any value.
+/// States without available actions keep zero value, as in the greedy backups.
+fn evaluation_sweep<'a, 'b, S, A, M, F>(
+    mdp: &'a M,
+    values: &mut [f64],
+    discount: f64,
+    select_action: F,
+) -> Result<f64, MDPError<'a, S>>
+where
+    S: State,
+    A: Action + 'b,
+    M: MDP<S, A>,
+    F: Fn(&S) -> Option<&'b A>,
+{
+    let mut delta = 0f64;
+    for state in mdp.states() {
+        let action = match select_action(state) {
+            Some(action) => action,
+            None => return Err(MDPError::NoAction { state }),
+        };
+        let new_value = if mdp
+            .actions()
+            .iter()
+            .any(|action| mdp.is_action_available(state, action))
+        {
+            q_value(mdp, state, action, values, discount)
+        } else {
+            0.0
+        };
+        delta = delta.max((values[state.id()] - new_value).abs());
+        values[state.id()] = new_value;
+    }
+    Ok(delta)
+}
+
+/// Returns the optimality gap of a policy, indexed by state ID, that is, `V*(s) - V_π(s)`,
+/// where the optimal values are computed by value iteration and the policy values by policy
+/// evaluation. The gap measures how far a hand-written or learned policy is from optimal in
+/// each state. Small negative gaps due to the accuracy of estimation are clamped to zero.
+///
+/// # Arguments
+///
+/// - `mdp` - Markov Decision Process.
+/// - `policy` - the policy of interest.
+/// - `theta` - small positive number determining the accuracy of estimation.
+/// - `max_iterations` - maximum iterations of value iteration and policy evaluation.
+pub fn optimality_gap<'a, S: State, A: Action, M: MDP<S, A>>(
+    mdp: &'a M,
+    policy: &Policy<S, A>,
+    theta: f64,
+    max_iterations: usize,
+) -> Result<Vec<f64>, MDPError<'a, S>> {
+    if mdp.n_states() == 0 || mdp.n_actions() == 0 {
+        return Err(MDPError::Empty);
+    }
+
+    let optimal_values = ValueIteration::default()
+        .with_theta(theta)
+        .with_max_iterations(max_iterations)
+        .solve(mdp);
+    let policy_values = evaluate_policy(mdp, policy, theta, max_iterations)?;
+
+    Ok(optimal_values
+        .iter()
+        .zip(policy_values)
+        .map(|(optimal, value)| (optimal - value).max(0.0))
+        .collect())
+}
+
+/// Returns the discount factor overriding the one of the MDP, if any, or else the discount
+/// factor of the MDP.
+fn discount_factor<S: State, A: Action, M: MDP<S, A>>(
+    discount_override: Option<f64>,
+    mdp: &M,
+) -> f64 {
+    match discount_override {
+        Some(gamma) if gamma <= 0.0 || gamma > 1.0 => panic!("Invalid gamma value: {gamma}"),
+        Some(gamma) => gamma,
+        None => mdp.discount_factor(),
+    }
+}
+
+/// Returns the expected value of taking an action in a state, according to the given values
+/// and discount factor.
+fn q_value<S: State, A: Action, M: MDP<S, A>>(
+    mdp: &M,
+    state: &S,
+    action: &A,
+    values: &[f64],
+    discount: f64,
+) -> f64 {
+    mdp.transitions(state, action)
+        .fold(0.0, |v, (next_state, p, r)| {
+            v + p * (r + discount * values[next_state.id()])
+        })
+}
+
+/// Returns the available action having the maximum expected value in a state along with
+/// its value. A state without available actions, e.g., a tile enclosed by walls, has zero
+/// value and the first action, as in the linear programming formulation.
+fn greedy_action<'a, S: State, A: Action, M: MDP<S, A>>(
+    mdp: &'a M,
+    state: &S,
+    values: &[f64],
+    discount: f64,
+) -> (&'a A, f64) {
+    let available_actions = mdp.available_actions(state);
+    if available_actions.is_empty() {
+        return (&mdp.actions()[0], 0.0);
+    }
+
+    let mut best_action = available_actions[0];
+    let mut best_value = f64::NEG_INFINITY;
+
+    for action in available_actions {
+        let v = q_value(mdp, state, action, values, discount);
+        if v > best_value {
+            best_value = v;
+            best_action = action;
+        }
+    }
+
+    (best_action, best_value)
+}
+
+/// Returns the policy that selects the greedy action in every state, according to the given
+/// values and discount factor.
+pub(crate) fn greedy_policy<'a, S: State, A: Action, M: MDP<S, A>>(
+    mdp: &'a M,
+    values: &[f64],
+    discount: f64,
+) -> Policy<'a, S, A> {
+    let mapping = mdp
+        .states()
+        .iter()
+        .map(|state| (state, greedy_action(mdp, state, values, discount).0))
+        .collect();
+    Policy::new(mapping)
+}
+
+/// Returns the available action having the maximum action value in a state, according to
+/// the given action values indexed by state ID and then by action ID.
+fn greedy_q_action<'a, S: State, A: Action, M: MDP<S, A>>(
+    mdp: &'a M,
+    state: &S,
+    q_table: &[Vec<f64>],
+) -> &'a A {
+    mdp.available_actions(state)
+        .into_iter()
+        .max_by(|a, b| q_table[state.id()][a.id()].total_cmp(&q_table[state.id()][b.id()]))
+        .unwrap_or(&mdp.actions()[0])
+}
+
+/// Returns a random available action with probability ε, or else the greedy action according
+/// to the given action values. This is the behavior policy of the temporal-difference methods.
+fn epsilon_greedy_action<'a, S: State, A: Action, M: MDP<S, A>, R: Rng + ?Sized>(
+    mdp: &'a M,
+    state: &S,
+    q_table: &[Vec<f64>],
+    epsilon: f64,
+    rng: &mut R,
+) -> &'a A {
+    if rng.gen_bool(epsilon) {
+        if let Some(&action) = mdp.available_actions(state).choose(rng) {
+            return action;
+        }
+    }
+    greedy_q_action(mdp, state, q_table)
+}
+
+/// Returns the probability of each available action under the ε-greedy policy, as selected
+/// by [`epsilon_greedy_action`], that is, the random choice may also select the greedy action.
+fn epsilon_greedy_probabilities<'a, S: State, A: Action, M: MDP<S, A>>(
+    mdp: &'a M,
+    state: &S,
+    q_table: &[Vec<f64>],
+    epsilon: f64,
+) -> Vec<(&'a A, f64)> {
+    let actions = mdp.available_actions(state);
+    if actions.is_empty() {
+        return vec![(greedy_q_action(mdp, state, q_table), 1.0)];
+    }
+
+    let greedy = greedy_q_action(mdp, state, q_table);
+    let exploration = epsilon / actions.len() as f64;
+    actions
+        .into_iter()
+        .map(|action| {
+            if action == greedy {
+                (action, 1.0 - epsilon + exploration)
+            } else {
+                (action, exploration)
+            }
+        })
+        .collect()
+}
+
+/// Returns the policy that is greedy with respect to the given action values.
+fn greedy_q_policy<'a, S: State, A: Action, M: MDP<S, A>>(
+    mdp: &'a M,
+    q_table: &[Vec<f64>],
+) -> Policy<'a, S, A> {
+    let mapping = mdp
+        .states()
+        .iter()
+        .map(|state| (state, greedy_q_action(mdp, state, q_table)))
+        .collect();
+    Policy::new(mapping)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::mdp::environment::{AsciiConfig, GridWorld, Move, Tile};
+    use crate::mdp::model::{MDPError, ShapedMDP, State, TransformedMDP, MDP};
+    #[cfg(feature = "lp")]
+    use crate::mdp::optimizer::LinearProgramming;
+    use crate::mdp::optimizer::{
+        average_reward_rate, discount_sweep, evaluate_policy, greedy_q_policy,
+        optimal_action_counts, optimality_gap, q_value, stationary_distribution,
+        verify_shaping_invariance, ExpectedSarsa, ModifiedPolicyIteration, MonteCarloControl,
+        NStepSarsa, Optimizer, PolicyIteration, PrioritizedSweeping, QLearning,
+        RealTimeDynamicProgramming, Sarsa, TdLambda, ValueIteration,
+    };
+    use crate::mdp::policy::Policy;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_policy_iteration() {
+        let grid = GridWorld::from(
+            3,
+            4,
+            |s| s.id() == 5, // wall
+            |a| match a {
+                Move::North => |d| match d {
+                    Move::North => 0.8,
+                    Move::South => 0.0,
+                    Move::East => 0.1,
+                    Move::West => 0.1,
+                },
+                Move::South => |d| match d {
+                    Move::North => 0.0,
+                    Move::South => 0.8,
+                    Move::East => 0.1,
+                    Move::West => 0.1,
+                },
+                Move::East => |d| match d {
+                    Move::North => 0.1,
+                    Move::South => 0.1,
+                    Move::East => 0.8,
+                    Move::West => 0.0,
+                },
+                Move::West => |d| match d {
+                    Move::North => 0.1,
+                    Move::South => 0.1,
+                    Move::East => 0.0,
+                    Move::West => 0.8,
+                },
+            },
+            |s| {
+                // states 3 and 7 are terminal, while every other state has a small negative reward
+                if s.id() == 3 {
+                    1.0
+                } else if s.id() == 7 {
+                    -1.0
+                } else {
+                    -0.5
+                }
+            },
+            |s| s.id() == 3 || s.id() == 7, // terminal states
+        )
+        .unwrap();
+
+        let optimal_policy = PolicyIteration::default()
+            .with_max_iterations(100000)
+            .find_optimal_policy(&grid)
+            .unwrap();
+
+        assert_eq!(
+            optimal_policy.select_action(&grid.states()[0]),
+            Some(&Move::East)
+        );
+        assert_eq!(
+            optimal_policy.select_action(&grid.states()[1]),
+            Some(&Move::East)
+        );
+        assert_eq!(
+            optimal_policy.select_action(&grid.states()[2]),
+            Some(&Move::East)
+        );
+        assert_eq!(
+            optimal_policy.select_action(&grid.states()[4]),
+            Some(&Move::North)
+        );
+        assert_eq!(
+            optimal_policy.select_action(&grid.states()[6]),
+            Some(&Move::North)
+        );
+        assert_eq!(
+            optimal_policy.select_action(&grid.states()[8]),
+            Some(&Move::North)
+        );
+        assert_eq!(
+            optimal_policy.select_action(&grid.states()[9]),
+            Some(&Move::East)
+        );
+        assert_eq!(
+            optimal_policy.select_action(&grid.states()[10]),
+            Some(&Move::North)
+        );
+        assert_eq!(
+            optimal_policy.select_action(&grid.states()[11]),
+            Some(&Move::North)
+        );
+    }
 
     #[test]
-    fn test_policy_iteration() {
+    fn test_value_iteration() {
         let grid = GridWorld::from(
             3,
             4,
@@ -211,12 +2066,10 @@ mod tests {
         )
         .unwrap();
 
-        let optimal_policy = PolicyIteration {
-            theta: 1e-6,
-            max_iterations: 100000,
-        }
-        .find_optimal_policy(&grid)
-        .unwrap();
+        let optimal_policy = ValueIteration::default()
+            .with_max_iterations(100000)
+            .find_optimal_policy(&grid)
+            .unwrap();
 
         assert_eq!(
             optimal_policy.select_action(&grid.states()[0]),
@@ -257,7 +2110,8 @@ mod tests {
     }
 
     #[test]
-    fn test_value_iteration() {
+    #[cfg(feature = "lp")]
+    fn test_linear_programming() {
         let grid = GridWorld::from(
             3,
             4,
@@ -302,48 +2156,815 @@ mod tests {
         )
         .unwrap();
 
-        let optimal_policy = ValueIteration {
-            theta: 1e-6,
-            max_iterations: 100000,
+        let expected = ValueIteration::default()
+            .with_theta(1e-12)
+            .with_max_iterations(100000)
+            .find_optimal_policy_with_values(&grid)
+            .unwrap();
+        let result = LinearProgramming
+            .find_optimal_policy_with_values(&grid)
+            .unwrap();
+
+        // the wall is unreachable, so its action has no effect
+        for state in grid.states().iter().filter(|s| s.id() != 5) {
+            assert_eq!(
+                result.policy.select_action(state),
+                expected.policy.select_action(state)
+            );
         }
-        .find_optimal_policy(&grid)
-        .unwrap();
+        for (value, expected_value) in result.values.iter().zip(&expected.values) {
+            assert!((value - expected_value).abs() < 1e-6);
+        }
+    }
 
+    #[test]
+    fn test_value_iteration_values() {
+        let grid = GridWorld::corner(3, 3, 1.0).unwrap();
+        let optimizer = ValueIteration::default().with_theta(1e-9);
+        let result = optimizer.find_optimal_policy_with_values(&grid).unwrap();
+
+        // on a deterministic grid each value is minus the distance to the closest corner
         assert_eq!(
-            optimal_policy.select_action(&grid.states()[0]),
-            Some(&Move::East)
-        );
-        assert_eq!(
-            optimal_policy.select_action(&grid.states()[1]),
-            Some(&Move::East)
-        );
-        assert_eq!(
-            optimal_policy.select_action(&grid.states()[2]),
-            Some(&Move::East)
-        );
-        assert_eq!(
-            optimal_policy.select_action(&grid.states()[4]),
-            Some(&Move::North)
+            result.values,
+            vec![0.0, -1.0, -2.0, -1.0, -2.0, -1.0, -2.0, -1.0, 0.0]
         );
+        assert_eq!(result.policy, optimizer.find_optimal_policy(&grid).unwrap());
+    }
+
+    #[test]
+    fn test_optimizer_report() {
+        let grid = GridWorld::corner(10, 10, 0.8).unwrap();
+
+        // a single sweep cannot propagate the values across a large grid
+        let (_, report) = ValueIteration::default()
+            .with_theta(1e-9)
+            .with_max_iterations(1)
+            .find_optimal_policy_with_report(&grid)
+            .unwrap();
+        assert_eq!(report.iterations, 1);
+        assert!(!report.converged);
+        assert!(report.final_delta >= 1e-9);
+
+        let (_, report) = PolicyIteration::default()
+            .with_theta(1e-9)
+            .with_max_iterations(1)
+            .find_optimal_policy_with_report(&grid)
+            .unwrap();
+        assert!(!report.converged);
+
+        // enough sweeps converge
+        let (_, report) = ValueIteration::default()
+            .with_theta(1e-9)
+            .find_optimal_policy_with_report(&grid)
+            .unwrap();
+        assert!(report.converged);
+        assert!(report.iterations < 10000);
+        assert!(report.final_delta < 1e-9);
+    }
+
+    #[test]
+    fn test_seeded_policy_iteration() {
+        let grid = GridWorld::corner(4, 4, 0.8).unwrap();
+        let optimizer = PolicyIteration::default().with_theta(1e-9);
+
+        // the same seed yields the same initial policy and thus the same run
+        let run = |seed| {
+            optimizer
+                .find_optimal_policy_with_rng(&grid, &mut StdRng::seed_from_u64(seed))
+                .unwrap()
+        };
+        let (policy, report) = run(7);
+        let (other_policy, other_report) = run(7);
+        assert_eq!(policy, other_policy);
+        assert_eq!(report, other_report);
+
+        // starting from the optimal policy, a single evaluation confirms it
+        let optimal = ValueIteration::default()
+            .with_theta(1e-9)
+            .find_optimal_policy(&grid)
+            .unwrap();
+        let (policy, _) = optimizer.find_optimal_policy_from(&grid, optimal).unwrap();
+        let gap = optimality_gap(&grid, &policy, 1e-9, 10000).unwrap();
+        assert!(gap.iter().all(|&g| g < 1e-6));
+    }
+
+    #[test]
+    fn test_warm_start() {
+        let grid = GridWorld::corner(4, 4, 0.8).unwrap();
+        let tweaked = TransformedMDP::new(GridWorld::corner(4, 4, 0.8).unwrap(), |r| 1.1 * r);
+        let optimizer = PolicyIteration::default().with_theta(1e-9);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let (policy, _) = optimizer
+            .find_optimal_policy_from(
+                &grid,
+                Policy::random_with_rng(grid.states(), grid.actions(), &mut rng),
+            )
+            .unwrap();
+        let (_, cold) = optimizer
+            .find_optimal_policy_from(
+                &tweaked,
+                Policy::random_with_rng(tweaked.states(), tweaked.actions(), &mut rng),
+            )
+            .unwrap();
+
+        // the solution of the original grid remains optimal after scaling the rewards
+        let (warm_policy, warm) = optimizer
+            .find_optimal_policy_from(&tweaked, policy)
+            .unwrap();
+        assert!(warm.iterations < cold.iterations);
+        let gap = optimality_gap(&tweaked, &warm_policy, 1e-9, 10000).unwrap();
+        assert!(gap.iter().all(|&g| g < 1e-6));
+
+        // an initial policy missing some states is rejected
+        assert!(matches!(
+            optimizer.find_optimal_policy_from(&grid, Policy::new(HashMap::new())),
+            Err(MDPError::NoAction { .. })
+        ));
+    }
+
+    #[test]
+    fn test_builder() {
+        let optimizer = ValueIteration::default()
+            .with_theta(1e-4)
+            .with_max_iterations(500)
+            .with_discount_override(0.9);
+        assert_eq!(optimizer.theta, 1e-4);
+        assert_eq!(optimizer.max_iterations, 500);
+        assert_eq!(optimizer.discount_override, Some(0.9));
+        assert!(optimizer.backup.is_none());
+        assert!(optimizer.in_place);
+
+        let optimizer = PolicyIteration::default().with_max_iterations(500);
+        assert_eq!(optimizer.theta, 1e-6);
+        assert_eq!(optimizer.max_iterations, 500);
+        assert_eq!(optimizer.discount_override, None);
+
+        // the defaults solve the corner problem
+        let grid = GridWorld::corner(4, 4, 0.8).unwrap();
+        let policy = PolicyIteration::default()
+            .find_optimal_policy(&grid)
+            .unwrap();
+        let gap = optimality_gap(&grid, &policy, 1e-9, 10000).unwrap();
+        assert!(gap.iter().all(|&g| g < 1e-4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid theta value: 0")]
+    fn test_invalid_theta() {
+        let _ = ValueIteration::default().with_theta(0.0);
+    }
+
+    #[test]
+    fn test_gauss_seidel() {
+        // the values propagate away from the goal, which is the first tile of each sweep
+        let grid = GridWorld::from_ascii(
+            "
+            G...
+            ....
+            ....
+            ....
+            ",
+            AsciiConfig::default(),
+        )
+        .unwrap();
+
+        let gauss_seidel = ValueIteration::default().with_theta(1e-9);
+        let jacobi = ValueIteration::default()
+            .with_theta(1e-9)
+            .with_in_place(false);
+
+        let (gauss_seidel_values, gauss_seidel_report) = gauss_seidel.solve_with_report(&grid);
+        let (jacobi_values, jacobi_report) = jacobi.solve_with_report(&grid);
+        assert!(gauss_seidel_report.converged);
+        assert!(jacobi_report.converged);
+        assert!(gauss_seidel_report.iterations < jacobi_report.iterations);
+
+        // both modes converge to the same values
+        for (a, b) in gauss_seidel_values.iter().zip(jacobi_values) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_modified_policy_iteration() {
+        let grid = GridWorld::corner(4, 4, 0.8).unwrap();
+        let result = ValueIteration::default()
+            .with_theta(1e-9)
+            .find_optimal_policy_with_values(&grid)
+            .unwrap();
+
+        let mut rounds = Vec::new();
+        for eval_sweeps in [1, 3, 10] {
+            let (policy, report) = ModifiedPolicyIteration::default()
+                .with_eval_sweeps(eval_sweeps)
+                .with_theta(1e-9)
+                .find_optimal_policy_with_report(&grid)
+                .unwrap();
+            assert!(report.converged);
+            rounds.push(report.iterations);
+
+            // the policy is optimal, although ties along the diagonal may be broken differently
+            for state in grid.states() {
+                let action = policy.select_action(state).unwrap();
+                let value = q_value(&grid, state, action, &result.values, 1.0);
+                assert!((value - result.values[state.id()]).abs() < 1e-6);
+            }
+        }
+
+        // more evaluation sweeps per round require fewer rounds
+        assert!(rounds.windows(2).all(|w| w[1] < w[0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid eval_sweeps value: 0")]
+    fn test_zero_eval_sweeps() {
+        let _ = ModifiedPolicyIteration::default().with_eval_sweeps(0);
+    }
+
+    #[test]
+    fn test_enclosed_tile() {
+        // the top right tile is enclosed by walls and the grid edge, so it has no actions
+        let grid = GridWorld::from_ascii("G.#.\n..##", AsciiConfig::default()).unwrap();
+        let enclosed = grid.states().iter().find(|s| (s.x, s.y) == (0, 3)).unwrap();
+        assert!(grid.available_actions(enclosed).is_empty());
+
+        let values = ValueIteration::default().with_theta(1e-9).solve(&grid);
+        assert!(values.iter().all(|v| v.is_finite()));
+        assert_eq!(values[enclosed.id()], 0.0);
+
+        let (_, report) = ModifiedPolicyIteration::default()
+            .with_theta(1e-9)
+            .find_optimal_policy_with_report(&grid)
+            .unwrap();
+        assert!(report.converged);
+        assert!(report.final_delta.is_finite());
+    }
+
+    #[test]
+    fn test_prioritized_sweeping() {
+        // the values propagate from the goal, in the opposite direction of the sweeps
+        let map = vec!["..........".repeat(2); 19]
+            .into_iter()
+            .chain([format!("{}G", ".".repeat(19))])
+            .collect::<Vec<_>>()
+            .join("\n");
+        let grid = GridWorld::from_ascii(&map, AsciiConfig::default()).unwrap();
+
+        let (values, report) = ValueIteration::default()
+            .with_theta(1e-9)
+            .solve_with_report(&grid);
+        let (policy, prioritized_report) = PrioritizedSweeping::default()
+            .with_theta(1e-9)
+            .find_optimal_policy_with_report(&grid)
+            .unwrap();
+        assert!(report.converged);
+        assert!(prioritized_report.converged);
+        assert!(prioritized_report.iterations * 10 < report.iterations * grid.n_states());
+
+        // the policy is optimal, although ties between moving east and south may be broken
+        // differently than value iteration does
+        for state in grid.states().iter().filter(|s| !grid.is_terminal(s)) {
+            let action = policy.select_action(state).unwrap();
+            assert!(
+                (q_value(&grid, state, action, &values, 1.0) - values[state.id()]).abs() < 1e-6
+            );
+        }
+    }
+
+    #[test]
+    fn test_prioritized_sweeping_budget() {
+        let grid = GridWorld::corner(4, 4, 0.8).unwrap();
+        let (_, report) = PrioritizedSweeping::default()
+            .with_max_backups(5)
+            .find_optimal_policy_with_report(&grid)
+            .unwrap();
+
+        assert_eq!(report.iterations, 5);
+        assert!(!report.converged);
+        assert!(report.final_delta > 0.0);
+    }
+
+    #[test]
+    fn test_greedy_from_values() {
+        let grid = GridWorld::corner(4, 4, 0.8).unwrap();
+        let result = ValueIteration::default()
+            .with_theta(1e-9)
+            .find_optimal_policy_with_values(&grid)
+            .unwrap();
+
         assert_eq!(
-            optimal_policy.select_action(&grid.states()[6]),
-            Some(&Move::North)
+            Policy::greedy_from_values(&grid, &result.values),
+            result.policy
         );
+    }
+
+    #[test]
+    fn test_large_grid() {
+        // sparse transitions keep the sweeps over large grids fast
+        let grid = GridWorld::corner(50, 50, 0.8).unwrap();
+        let result = ValueIteration::default()
+            .find_optimal_policy_with_values(&grid)
+            .unwrap();
+
+        // failed moves are free, so the tile next to the corner costs a single successful move
+        assert!((result.values[1] + 1.0).abs() < 1e-4);
+        assert!(result.values.iter().all(|&v| v <= 0.0));
+    }
+
+    #[test]
+    fn test_real_time_dynamic_programming() {
+        let grid = GridWorld::corner(4, 4, 0.8).unwrap();
+
+        let values = RealTimeDynamicProgramming {
+            starting_state: 6,
+            epsilon: 0.1,
+            episodes: 500,
+            max_steps: 100,
+        }
+        .learn(&grid, &mut StdRng::seed_from_u64(0))
+        .unwrap();
+        let policy = Policy::greedy_from_values(&grid, &values);
+
+        // terminal states are never backed up, while the starting state is always visited
+        assert_eq!(values[0], 0.0);
+        assert_eq!(values[15], 0.0);
+        assert!(values[6] < 0.0);
+
+        // the greedy policy should lead from the starting state to a terminal state
+        let episode = grid.run_policy(&policy, &grid.states()[6], 1000).unwrap();
+        assert!(grid.is_terminal(episode.trajectory.last().unwrap()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid starting_state value: 16")]
+    fn test_invalid_rtdp_starting_state() {
+        let grid = GridWorld::corner(4, 4, 0.8).unwrap();
+        let _ = RealTimeDynamicProgramming {
+            starting_state: 16,
+            epsilon: 0.1,
+            episodes: 1,
+            max_steps: 1,
+        }
+        .learn(&grid, &mut StdRng::seed_from_u64(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid epsilon value: 1.5")]
+    fn test_invalid_rtdp_epsilon() {
+        let grid = GridWorld::corner(4, 4, 0.8).unwrap();
+        let _ = RealTimeDynamicProgramming {
+            starting_state: 6,
+            epsilon: 1.5,
+            episodes: 1,
+            max_steps: 1,
+        }
+        .learn(&grid, &mut StdRng::seed_from_u64(0));
+    }
+
+    #[test]
+    fn test_contraction_rates() {
+        let grid = GridWorld::corner(4, 4, 0.8).unwrap();
+
+        let rates = ValueIteration::default()
+            .with_max_iterations(1000)
+            .contraction_rates(&grid);
+
+        // the grid is undiscounted, but the absorbing corners still contract the values
+        assert!(!rates.is_empty());
+        assert!(rates.iter().all(|&rate| rate <= 1.0 + 1e-9));
+    }
+
+    #[test]
+    fn test_custom_backup() {
+        let grid = GridWorld::corner(3, 3, 1.0).unwrap();
+        let optimizer = ValueIteration {
+            theta: 1e-6,
+            max_iterations: 1,
+            backup: Some(Box::new(|action_values| action_values.iter().sum())),
+            discount_override: None,
+            in_place: true,
+        };
+
+        // the first backed up state sums the immediate rewards of its three available actions
+        let mut values = vec![0.0; grid.n_states()];
+        optimizer.sweep(&grid, &mut values);
+        assert_eq!(values[0], 0.0);
+        assert_eq!(values[1], -3.0);
+
+        // a maximum backup is equivalent to the default one
+        let optimizer = ValueIteration {
+            theta: 1e-6,
+            max_iterations: 1000,
+            backup: Some(Box::new(|action_values| {
+                action_values
+                    .iter()
+                    .copied()
+                    .fold(f64::NEG_INFINITY, f64::max)
+            })),
+            discount_override: None,
+            in_place: true,
+        };
+        let default = ValueIteration {
+            backup: None,
+            ..optimizer
+        };
         assert_eq!(
-            optimal_policy.select_action(&grid.states()[8]),
-            Some(&Move::North)
+            optimizer.contraction_rates(&grid),
+            default.contraction_rates(&grid)
         );
-        assert_eq!(
-            optimal_policy.select_action(&grid.states()[9]),
-            Some(&Move::East)
+    }
+
+    #[test]
+    fn test_discount_sweep() {
+        let grid = GridWorld::corner(3, 3, 0.8).unwrap();
+        let sweep = discount_sweep(&grid, &[0.5, 1.0], 1e-9, 10000);
+
+        assert_eq!(sweep.len(), 2);
+        assert_eq!((sweep[0].0, sweep[1].0), (0.5, 1.0));
+
+        // future penalties weigh less under a smaller discount, so the values are higher
+        let (myopic, farsighted) = (&sweep[0].1, &sweep[1].1);
+        assert_eq!((myopic[0], farsighted[0]), (0.0, 0.0));
+        assert!(myopic[4] > farsighted[4]);
+
+        // the undiscounted values match those of plain value iteration
+        let result = ValueIteration::default()
+            .with_theta(1e-9)
+            .find_optimal_policy_with_values(&grid)
+            .unwrap();
+        assert_eq!(&result.values, farsighted);
+    }
+
+    #[test]
+    fn test_discounted_grid() {
+        let grid = GridWorld::corner(3, 3, 1.0)
+            .unwrap()
+            .with_discount_factor(0.9);
+        let result = ValueIteration::default()
+            .with_theta(1e-9)
+            .find_optimal_policy_with_values(&grid)
+            .unwrap();
+
+        // the second step towards a corner is discounted
+        assert_eq!(result.values[1], -1.0);
+        assert!((result.values[2] + 1.9).abs() < 1e-9);
+
+        // policy iteration reaches a corner from the center within the optimal two steps
+        let policy = PolicyIteration::default()
+            .with_theta(1e-9)
+            .find_optimal_policy(&grid)
+            .unwrap();
+        let episode = grid.run_policy(&policy, &grid.states()[4], 2).unwrap();
+        assert!(grid.is_terminal(episode.trajectory.last().unwrap()));
+    }
+
+    #[test]
+    fn test_discount_override() {
+        let grid = GridWorld::corner(3, 3, 0.8).unwrap();
+
+        // an override of one is equivalent to the undiscounted grid
+        let policy = PolicyIteration {
+            theta: 1e-9,
+            max_iterations: 10000,
+            discount_override: Some(1.0),
+        }
+        .find_optimal_policy(&grid)
+        .unwrap();
+        let episode = grid.run_policy(&policy, &grid.states()[4], 1000).unwrap();
+        assert!(grid.is_terminal(episode.trajectory.last().unwrap()));
+
+        // the overridden discount shrinks the values of value iteration
+        let discounted = ValueIteration {
+            theta: 1e-9,
+            max_iterations: 10000,
+            backup: None,
+            discount_override: Some(0.5),
+            in_place: true,
+        }
+        .find_optimal_policy_with_values(&grid)
+        .unwrap();
+        assert!(discounted.values.iter().all(|&v| v > -2.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid gamma value: 1.5")]
+    fn test_invalid_discount_override() {
+        let _ = PolicyIteration::default().with_discount_override(1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid gamma value: 0")]
+    fn test_zero_discount_override() {
+        let grid = GridWorld::corner(3, 3, 0.8).unwrap();
+        let _ = PolicyIteration {
+            theta: 1e-9,
+            max_iterations: 10000,
+            discount_override: Some(0.0),
+        }
+        .find_optimal_policy(&grid);
+    }
+
+    #[test]
+    fn test_shaping_invariance() {
+        // the potential rewards tiles closer to the bottom-right corner
+        let shaped = ShapedMDP::new(GridWorld::corner(4, 4, 0.8).unwrap(), |tile: &Tile| {
+            (tile.x + tile.y) as f64
+        });
+        assert!(verify_shaping_invariance(
+            shaped.inner(),
+            &shaped,
+            1e-9,
+            10000
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_random_policy() {
+        let grid = GridWorld::corner(3, 3, 0.8)
+            .unwrap()
+            .with_discount_factor(0.9);
+        let policy = Policy::random(grid.states(), grid.actions());
+        let values = evaluate_policy(&grid, &policy, 1e-9, 10000).unwrap();
+
+        // terminal corners are worth nothing, while every other tile has a cost, which is
+        // bounded by the discounted cost of never terminating
+        assert_eq!((values[0], values[8]), (0.0, 0.0));
+        assert!(values[1..8].iter().all(|v| (-10.0 - 1e-6..0.0).contains(v)));
+
+        // an incomplete policy cannot be evaluated
+        let policy = Policy::new(HashMap::from([(&grid.states()[0], &Move::North)]));
+        assert!(evaluate_policy(&grid, &policy, 1e-9, 10000).is_err());
+    }
+
+    #[test]
+    fn test_optimality_gap() {
+        let grid = GridWorld::corner(3, 3, 0.8).unwrap();
+        let optimal = ValueIteration::default()
+            .with_theta(1e-9)
+            .find_optimal_policy_with_values(&grid)
+            .unwrap();
+
+        // the optimal policy has no gap and its values match the optimal values
+        let gap = optimality_gap(&grid, &optimal.policy, 1e-9, 10000).unwrap();
+        assert!(gap.iter().all(|&g| g < 1e-6));
+        let values = evaluate_policy(&grid, &optimal.policy, 1e-9, 10000).unwrap();
+        assert!(values
+            .iter()
+            .zip(&optimal.values)
+            .all(|(v, v_opt)| (v - v_opt).abs() < 1e-6));
+
+        // always moving in the same direction is suboptimal in some states
+        let policy = Policy::new(
+            grid.states()
+                .iter()
+                .map(|state| (state, &grid.actions()[0]))
+                .collect(),
         );
-        assert_eq!(
-            optimal_policy.select_action(&grid.states()[10]),
-            Some(&Move::North)
+        let gap = optimality_gap(&grid, &policy, 1e-9, 10000).unwrap();
+        assert!(gap.iter().all(|&g| g >= 0.0));
+        assert!(gap.iter().any(|&g| g > 1e-3));
+    }
+
+    #[test]
+    fn test_q_learning() {
+        let grid = GridWorld::corner(3, 3, 1.0).unwrap();
+        let q_table = QLearning {
+            alpha: 0.5,
+            epsilon: 0.2,
+            episodes: 2000,
+            max_steps_per_episode: 100,
+        }
+        .learn(&grid, &mut StdRng::seed_from_u64(7))
+        .unwrap();
+        let policy = greedy_q_policy(&grid, &q_table);
+
+        // on a deterministic grid the learned policy takes a shortest path from every tile
+        let gap = optimality_gap(&grid, &policy, 1e-9, 10000).unwrap();
+        assert!(gap.iter().all(|&g| g < 1e-6));
+    }
+
+    #[test]
+    fn test_monte_carlo_control() {
+        let grid = GridWorld::corner(3, 3, 1.0).unwrap();
+        let q_table = MonteCarloControl {
+            epsilon: 0.1,
+            episodes: 2000,
+            max_steps: 100,
+        }
+        .learn(&grid, &mut StdRng::seed_from_u64(7))
+        .unwrap();
+        let policy = greedy_q_policy(&grid, &q_table);
+
+        // the learned policy reaches a terminal corner from every tile in a few steps
+        for state in grid.states() {
+            let episode = grid.run_policy(&policy, state, 10).unwrap();
+            assert!(grid.is_terminal(episode.trajectory.last().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_sarsa() {
+        let grid = GridWorld::corner(3, 3, 1.0).unwrap();
+        let q_table = Sarsa {
+            alpha: 0.1,
+            epsilon: 0.1,
+            episodes: 2000,
+            max_steps: 100,
+        }
+        .learn(&grid, &mut StdRng::seed_from_u64(7))
+        .unwrap();
+        let policy = greedy_q_policy(&grid, &q_table);
+
+        // the learned policy reaches a terminal corner from every tile in a few steps
+        for state in grid.states() {
+            let episode = grid.run_policy(&policy, state, 10).unwrap();
+            assert!(grid.is_terminal(episode.trajectory.last().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_expected_sarsa() {
+        let grid = GridWorld::corner(3, 3, 1.0).unwrap();
+        let optimizer = ExpectedSarsa {
+            alpha: 0.5,
+            epsilon: 0.1,
+            episodes: 2000,
+            max_steps: 100,
+        };
+
+        let q_table = optimizer
+            .learn(&grid, &mut StdRng::seed_from_u64(7))
+            .unwrap();
+        assert!(q_table.iter().flatten().all(|q| q.is_finite()));
+
+        // on a deterministic grid the learned policy is optimal, as the one of value iteration
+        let policy = greedy_q_policy(&grid, &q_table);
+        let gap = optimality_gap(&grid, &policy, 1e-9, 10000).unwrap();
+        assert!(gap.iter().all(|&g| g < 1e-6));
+    }
+
+    #[test]
+    fn test_n_step_sarsa() {
+        let grid = GridWorld::corner(4, 4, 1.0).unwrap();
+
+        // a single step is identical to SARSA
+        let q_table = NStepSarsa {
+            n: 1,
+            alpha: 0.1,
+            epsilon: 0.1,
+            episodes: 100,
+            max_steps: 100,
+        }
+        .learn(&grid, &mut StdRng::seed_from_u64(7))
+        .unwrap();
+        let sarsa_q_table = Sarsa {
+            alpha: 0.1,
+            epsilon: 0.1,
+            episodes: 100,
+            max_steps: 100,
+        }
+        .learn(&grid, &mut StdRng::seed_from_u64(7))
+        .unwrap();
+        assert_eq!(q_table, sarsa_q_table);
+
+        // windows longer than the episodes are truncated at the terminal states
+        for n in [1, 3, 10] {
+            let q_table = NStepSarsa {
+                n,
+                alpha: 0.1,
+                epsilon: 0.1,
+                episodes: 2000,
+                max_steps: 100,
+            }
+            .learn(&grid, &mut StdRng::seed_from_u64(7))
+            .unwrap();
+            let policy = greedy_q_policy(&grid, &q_table);
+
+            // the learned policy reaches a terminal corner from every tile in a few steps
+            for state in grid.states() {
+                let episode = grid.run_policy(&policy, state, 10).unwrap();
+                assert!(grid.is_terminal(episode.trajectory.last().unwrap()));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid n value: 0")]
+    fn test_zero_step_sarsa() {
+        let grid = GridWorld::corner(3, 3, 1.0).unwrap();
+        let _ = NStepSarsa {
+            n: 0,
+            alpha: 0.1,
+            epsilon: 0.1,
+            episodes: 1,
+            max_steps: 1,
+        }
+        .learn(&grid, &mut rand::thread_rng());
+    }
+
+    #[test]
+    fn test_required_iterations() {
+        // ln(0.01 * 0.1) / ln(0.9) = 65.56
+        assert_eq!(ValueIteration::required_iterations(0.9, 0.01, 1.0), 66);
+        // larger rewards require more sweeps
+        assert!(
+            ValueIteration::required_iterations(0.9, 0.01, 10.0)
+                > ValueIteration::required_iterations(0.9, 0.01, 1.0)
         );
+        // the accuracy is already met by the initial values
+        assert_eq!(ValueIteration::required_iterations(0.5, 10.0, 1.0), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid gamma value: 1")]
+    fn test_required_iterations_undiscounted() {
+        ValueIteration::required_iterations(1.0, 0.01, 1.0);
+    }
+
+    #[test]
+    fn test_td_lambda() {
+        let grid = GridWorld::corner(3, 3, 0.8).unwrap();
+        let policy = ValueIteration::default()
+            .with_max_iterations(1000)
+            .find_optimal_policy(&grid)
+            .unwrap();
+
+        let (values, changes) = TdLambda {
+            lambda: 0.8,
+            alpha: 0.05,
+            episodes: 500,
+            max_steps: 100,
+        }
+        .evaluate(&grid, &policy, &mut StdRng::seed_from_u64(7))
+        .unwrap();
+
+        // terminal states have zero value, while every other state costs at least one step
+        assert_eq!(values[0], 0.0);
+        assert_eq!(values[8], 0.0);
+        assert!(values[1..8].iter().all(|&v| v < 0.0));
+
+        // the values settle as the evaluation proceeds
+        assert_eq!(changes.len(), 500);
+        let early: f64 = changes[..50].iter().sum();
+        let late: f64 = changes[450..].iter().sum();
+        assert!(late < early);
+    }
+
+    #[test]
+    fn test_optimal_action_counts() {
+        // moves always succeed, so the optimal values are the distances to the nearest corner
+        let grid = GridWorld::corner(3, 3, 1.0).unwrap();
+        let values = vec![0.0, -1.0, -2.0, -1.0, -2.0, -1.0, -2.0, -1.0, 0.0];
+
+        let counts = optimal_action_counts(&grid, &values, 1e-6);
+
+        // the center is equally far from both corners along four paths, while a tile next to
+        // a corner has a single optimal move
+        assert_eq!(counts[4], 4);
+        assert_eq!(counts[1], 1);
+        assert_eq!(counts[3], 1);
+    }
+
+    #[test]
+    fn test_average_reward_rate() {
+        // a continuing task where the agent moves back and forth between two tiles
+        let grid = GridWorld::from(
+            1,
+            2,
+            |_| false,
+            |a| match a {
+                Move::North => |d| if *d == Move::North { 1.0 } else { 0.0 },
+                Move::South => |d| if *d == Move::South { 1.0 } else { 0.0 },
+                Move::East => |d| if *d == Move::East { 1.0 } else { 0.0 },
+                Move::West => |d| if *d == Move::West { 1.0 } else { 0.0 },
+            },
+            |s| if s.id() == 1 { 2.0 } else { 0.0 },
+            |_| false,
+        )
+        .unwrap();
+
+        let policy = Policy::new(HashMap::from([
+            (&grid.states()[0], &Move::East),
+            (&grid.states()[1], &Move::West),
+        ]));
+
+        // half of the steps enter the rewarding tile
         assert_eq!(
-            optimal_policy.select_action(&grid.states()[11]),
-            Some(&Move::North)
+            stationary_distribution(&grid, &policy, 10).unwrap(),
+            vec![0.5, 0.5]
         );
+        assert_eq!(average_reward_rate(&grid, &policy, 10).unwrap(), 1.0);
+
+        // in an episodic task, the agent is eventually absorbed by the terminal states
+        let grid = GridWorld::corner(3, 3, 0.8).unwrap();
+        let policy = ValueIteration::default()
+            .with_max_iterations(1000)
+            .find_optimal_policy(&grid)
+            .unwrap();
+
+        let distribution = stationary_distribution(&grid, &policy, 1000).unwrap();
+        assert!((distribution[0] + distribution[8] - 1.0).abs() < 1e-6);
+        assert!(average_reward_rate(&grid, &policy, 1000).unwrap().abs() < 1e-6);
     }
 }
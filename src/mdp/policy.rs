@@ -1,5 +1,8 @@
-use crate::mdp::model::{Action, State};
+use crate::mdp::model::{Action, State, MDP};
+use crate::mdp::optimizer::greedy_policy;
+use rand::distributions::{Distribution, WeightedIndex};
 use rand::seq::SliceRandom;
+use rand::Rng;
 use std::collections::HashMap;
 
 /// Represents a policy in a Markov Decision Process (MDP), which defines a mapping
@@ -60,17 +63,44 @@ impl<'a, S: State, A: Action> Policy<'a, S, A> {
     /// - `states` - an iterator over states
     /// - `actions` - an iterator over actions
     pub fn random(states: &'a [S], actions: &'a [A]) -> Self {
-        let mut rng = rand::thread_rng();
+        Self::random_with_rng(states, actions, &mut rand::thread_rng())
+    }
+
+    /// Creates a uniform at random policy using the given random number generator, e.g., a
+    /// seeded one for reproducible policies.
+    ///
+    /// # Arguments
+    ///
+    /// - `states` - an iterator over states
+    /// - `actions` - an iterator over actions
+    /// - `rng` - the random number generator used to select the actions
+    pub fn random_with_rng<R: Rng + ?Sized>(
+        states: &'a [S],
+        actions: &'a [A],
+        rng: &mut R,
+    ) -> Self {
         let mapping = states
             .iter()
             .map(|state| {
-                let action = actions.choose(&mut rng).expect("Actions must not be empty");
+                let action = actions.choose(rng).expect("Actions must not be empty");
                 (state, action)
             })
             .collect();
         Self { mapping }
     }
 
+    /// Creates the greedy policy with respect to the given state values, that is, the policy
+    /// selecting in each state the available action maximizing the one-step Bellman backup
+    /// `Σ_s' P(s'|s,a) (R(s,a,s') + γ V(s'))`, using the discount factor of the MDP.
+    ///
+    /// # Arguments
+    ///
+    /// - `mdp` - the Markov Decision Process
+    /// - `values` - the state values indexed by state ID
+    pub fn greedy_from_values<M: MDP<S, A>>(mdp: &'a M, values: &[f64]) -> Self {
+        greedy_policy(mdp, values, mdp.discount_factor())
+    }
+
     /// Returns the corresponding policy action for the given state, or None if
     /// there is no action assigned to the given state.
     ///
@@ -80,14 +110,291 @@ impl<'a, S: State, A: Action> Policy<'a, S, A> {
     pub fn select_action(&self, state: &S) -> Option<&A> {
         self.mapping.get(state).copied()
     }
+
+    /// Consumes the policy and returns the mapping from states to actions.
+    pub(crate) fn into_mapping(self) -> HashMap<&'a S, &'a A> {
+        self.mapping
+    }
+}
+
+/// Represents a stochastic policy in a Markov Decision Process (MDP), which maps each state
+/// to a probability distribution over actions, rather than a single action. Stochastic
+/// policies can represent exploratory behavior, such as ε-greedy or softmax policies.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use readapt::mdp::policy::StochasticPolicy;
+/// use readapt::mdp::model::{State, Action};
+///
+/// #[derive(Debug, Hash, PartialEq, Eq)]
+/// struct S { id: usize }
+///
+/// #[derive(Debug, PartialEq, Eq)]
+/// struct A { id: usize }
+///
+/// impl State for S {
+///     fn id(&self) -> usize { self.id }
+/// }
+///
+/// impl Action for A {
+///     fn id(&self) -> usize { self.id }
+/// }
+///
+/// let state = S { id: 0 };
+/// let (left, right) = (A { id: 0 }, A { id: 1 });
+/// let policy = StochasticPolicy::new(HashMap::from([(&state, vec![(&left, 0.25), (&right, 0.75)])]));
+///
+/// assert_eq!(policy.action_probability(&state, &right), 0.75);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct StochasticPolicy<'a, S: State, A: Action> {
+    mapping: HashMap<&'a S, Vec<(&'a A, f64)>>,
+}
+
+impl<'a, S: State, A: Action> StochasticPolicy<'a, S, A> {
+    /// Creates a custom stochastic policy.
+    ///
+    /// # Arguments
+    ///
+    /// - `mapping` - a hash map from states to action probabilities
+    pub fn new(mapping: HashMap<&'a S, Vec<(&'a A, f64)>>) -> Self {
+        Self { mapping }
+    }
+
+    /// Returns the probability of selecting the given action in the given state. The
+    /// probability is zero if the action, or the state, is not part of the policy.
+    ///
+    /// # Arguments
+    ///
+    /// - `state` - the state of interest
+    /// - `action` - the action of interest
+    pub fn action_probability(&self, state: &S, action: &A) -> f64 {
+        self.mapping
+            .get(state)
+            .and_then(|actions| actions.iter().find(|(a, _)| *a == action))
+            .map(|&(_, p)| p)
+            .unwrap_or(0.0)
+    }
+
+    /// Samples an action for the given state according to the action probabilities, or returns
+    /// None if the state is not part of the policy or its probabilities are invalid, e.g.,
+    /// they are all zero.
+    ///
+    /// # Arguments
+    ///
+    /// - `state` - the state of interest
+    /// - `rng` - the random number generator used to sample the action
+    pub fn sample_action<R: Rng + ?Sized>(&self, state: &S, rng: &mut R) -> Option<&'a A> {
+        let actions = self.mapping.get(state)?;
+        let distribution = WeightedIndex::new(actions.iter().map(|(_, p)| *p)).ok()?;
+        Some(actions[distribution.sample(rng)].0)
+    }
+
+    /// Returns the deterministic policy that maps each state to its most probable action,
+    /// that is, the mode of the action distribution. Ties are broken in favor of the action
+    /// having the lowest ID, so that the result is deterministic. States having no actions
+    /// are not part of the resulting policy.
+    pub fn to_deterministic(&self) -> Policy<'a, S, A> {
+        let mapping = self
+            .mapping
+            .iter()
+            .filter_map(|(&state, actions)| {
+                actions
+                    .iter()
+                    .max_by(|(a, p), (b, q)| p.total_cmp(q).then(b.id().cmp(&a.id())))
+                    .map(|&(action, _)| (state, action))
+            })
+            .collect();
+
+        Policy::new(mapping)
+    }
+
+    /// Returns the average Shannon entropy of the action distributions over the given states,
+    /// ignoring states that are not part of the policy. A low mean entropy indicates a nearly
+    /// deterministic policy, while a high one indicates a very exploratory policy.
+    ///
+    /// # Arguments
+    ///
+    /// - `states` - the states over which the entropy is averaged
+    pub fn mean_entropy(&self, states: &[S]) -> f64 {
+        let entropies: Vec<f64> = states
+            .iter()
+            .filter_map(|state| self.mapping.get(state))
+            .map(|actions| {
+                actions
+                    .iter()
+                    .filter(|(_, p)| *p > 0.0)
+                    .map(|(_, p)| -p * f64::ln(*p))
+                    .sum()
+            })
+            .collect();
+
+        if entropies.is_empty() {
+            0.0
+        } else {
+            entropies.iter().sum::<f64>() / entropies.len() as f64
+        }
+    }
+
+    /// Returns the cross-entropy of this policy relative to a reference policy, summed over
+    /// the given states, that is, `-Σ_s Σ_a p_ref(a|s) ln p(a|s)`. The cross-entropy is
+    /// minimized when the policy matches the reference and it is infinite if the policy
+    /// assigns zero probability to an action that the reference may select.
+    ///
+    /// # Arguments
+    ///
+    /// - `reference` - the reference policy, e.g., the optimal policy
+    /// - `states` - the states over which the cross-entropy is summed
+    pub fn cross_entropy(&self, reference: &StochasticPolicy<S, A>, states: &[S]) -> f64 {
+        states
+            .iter()
+            .filter_map(|state| reference.mapping.get(state).map(|actions| (state, actions)))
+            .flat_map(|(state, actions)| {
+                actions
+                    .iter()
+                    .filter(|(_, p_ref)| *p_ref > 0.0)
+                    .map(move |(action, p_ref)| {
+                        -p_ref * f64::ln(self.action_probability(state, action))
+                    })
+            })
+            .sum()
+    }
+}
+
+impl<'a, S: State, A: Action> From<Policy<'a, S, A>> for StochasticPolicy<'a, S, A> {
+    /// Converts a deterministic policy into a stochastic one, which selects the action of the
+    /// deterministic policy with probability one.
+    fn from(policy: Policy<'a, S, A>) -> Self {
+        let mapping = policy
+            .mapping
+            .into_iter()
+            .map(|(state, action)| (state, vec![(action, 1.0)]))
+            .collect();
+
+        Self { mapping }
+    }
+}
+
+/// Wraps a deterministic policy so that it explores, that is, in every state it takes the
+/// action of the base policy with probability `1 - ε`, or else an action uniformly at random
+/// among all actions, including the base one. It is useful for measuring how much exploration
+/// hurts the return of a converged policy, e.g., by running it through
+/// [`MDP::run_stochastic_policy`] after converting it into a [`StochasticPolicy`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use readapt::mdp::policy::{EpsilonGreedyPolicy, Policy, StochasticPolicy};
+/// use readapt::mdp::model::{State, Action};
+///
+/// #[derive(Debug, Hash, PartialEq, Eq)]
+/// struct S { id: usize }
+///
+/// #[derive(Debug, PartialEq, Eq)]
+/// struct A { id: usize }
+///
+/// impl State for S {
+///     fn id(&self) -> usize { self.id }
+/// }
+///
+/// impl Action for A {
+///     fn id(&self) -> usize { self.id }
+/// }
+///
+/// let state = S { id: 0 };
+/// let actions = [A { id: 0 }, A { id: 1 }];
+/// let base = Policy::new(HashMap::from([(&state, &actions[1])]));
+/// let policy = StochasticPolicy::from(EpsilonGreedyPolicy::new(base, 0.2, &actions));
+///
+/// assert_eq!(policy.action_probability(&state, &actions[0]), 0.1);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct EpsilonGreedyPolicy<'a, S: State, A: Action> {
+    base: Policy<'a, S, A>,
+    epsilon: f64,
+    actions: &'a [A],
+}
+
+impl<'a, S: State, A: Action> EpsilonGreedyPolicy<'a, S, A> {
+    /// Creates an ε-greedy policy around a deterministic one.
+    ///
+    /// # Arguments
+    ///
+    /// - `base` - the deterministic policy, e.g., a converged one
+    /// - `epsilon` - the probability of taking a random action, between 0 and 1
+    /// - `actions` - the actions among which the random action is selected
+    pub fn new(base: Policy<'a, S, A>, epsilon: f64, actions: &'a [A]) -> Self {
+        if !(0.0..=1.0).contains(&epsilon) {
+            panic!("Invalid epsilon value: {epsilon}");
+        }
+
+        Self {
+            base,
+            epsilon,
+            actions,
+        }
+    }
+
+    /// Returns a random action with probability ε, or else the action of the base policy, or
+    /// None if the state is not part of the base policy.
+    ///
+    /// # Arguments
+    ///
+    /// - `state` - the state of interest
+    /// - `rng` - the random number generator used to select the random action
+    pub fn select_action<R: Rng + ?Sized>(&self, state: &S, rng: &mut R) -> Option<&'a A> {
+        let action = self.base.mapping.get(state).copied()?;
+        if rng.gen_bool(self.epsilon) {
+            self.actions.choose(rng)
+        } else {
+            Some(action)
+        }
+    }
+}
+
+impl<'a, S: State, A: Action> From<EpsilonGreedyPolicy<'a, S, A>> for StochasticPolicy<'a, S, A> {
+    /// Converts an ε-greedy policy into a stochastic one, where every action has probability
+    /// `ε / |A|`, apart from the action of the base policy, which has `1 - ε + ε / |A|`.
+    fn from(policy: EpsilonGreedyPolicy<'a, S, A>) -> Self {
+        let exploration = policy.epsilon / policy.actions.len() as f64;
+        let mapping = policy
+            .base
+            .mapping
+            .into_iter()
+            .map(|(state, base_action)| {
+                let actions = policy
+                    .actions
+                    .iter()
+                    .map(|action| {
+                        let p = if action == base_action {
+                            1.0 - policy.epsilon + exploration
+                        } else {
+                            exploration
+                        };
+                        (action, p)
+                    })
+                    .collect();
+                (state, actions)
+            })
+            .collect();
+
+        Self { mapping }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use crate::mdp::{
         model::{Action, State},
-        policy::Policy,
+        policy::{EpsilonGreedyPolicy, Policy, StochasticPolicy},
     };
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[derive(Debug, Hash, PartialEq, Eq)]
     struct S {
@@ -128,4 +435,139 @@ mod tests {
         // there should be no action for state 10
         assert!(random_policy.select_action(&S { id: 10 }).is_none());
     }
+
+    #[test]
+    fn cross_entropy() {
+        let states: Vec<S> = (0..2).map(|id| S { id }).collect();
+        let actions: Vec<A> = (0..2).map(|id| A { id }).collect();
+
+        let uniform = StochasticPolicy::new(
+            states
+                .iter()
+                .map(|state| (state, vec![(&actions[0], 0.5), (&actions[1], 0.5)]))
+                .collect(),
+        );
+        let deterministic = StochasticPolicy::new(HashMap::from([
+            (&states[0], vec![(&actions[0], 1.0)]),
+            (&states[1], vec![(&actions[1], 1.0)]),
+        ]));
+
+        // each state contributes ln 2 when the uniform policy is compared to any policy
+        let h = uniform.cross_entropy(&deterministic, &states);
+        assert!((h - 2.0 * f64::ln(2.0)).abs() < 1e-12);
+        let h = uniform.cross_entropy(&uniform, &states);
+        assert!((h - 2.0 * f64::ln(2.0)).abs() < 1e-12);
+
+        // a deterministic policy never selecting the reference actions is infinitely far
+        assert_eq!(
+            deterministic.cross_entropy(&uniform, &states),
+            f64::INFINITY
+        );
+        assert_eq!(deterministic.cross_entropy(&deterministic, &states), 0.0);
+    }
+
+    #[test]
+    fn mean_entropy() {
+        let states: Vec<S> = (0..2).map(|id| S { id }).collect();
+        let actions: Vec<A> = (0..4).map(|id| A { id }).collect();
+
+        let policy = StochasticPolicy::new(HashMap::from([
+            (
+                &states[0],
+                actions.iter().map(|action| (action, 0.25)).collect(),
+            ),
+            (&states[1], vec![(&actions[0], 1.0)]),
+        ]));
+
+        // the uniform state has entropy ln 4 and the deterministic one has zero entropy
+        assert!((policy.mean_entropy(&states) - f64::ln(4.0) / 2.0).abs() < 1e-12);
+        assert_eq!(policy.mean_entropy(&states[1..]), 0.0);
+        assert_eq!(policy.mean_entropy(&[]), 0.0);
+    }
+
+    #[test]
+    fn sample_action() {
+        let states: Vec<S> = (0..3).map(|id| S { id }).collect();
+        let actions: Vec<A> = (0..2).map(|id| A { id }).collect();
+
+        let policy = StochasticPolicy::new(HashMap::from([
+            (&states[0], vec![(&actions[0], 0.0), (&actions[1], 1.0)]),
+            (&states[1], vec![(&actions[0], 0.5), (&actions[1], 0.5)]),
+            (&states[2], vec![(&actions[0], 0.0)]),
+        ]));
+        let mut rng = StdRng::seed_from_u64(7);
+
+        assert!((0..100).all(|_| policy.sample_action(&states[0], &mut rng) == Some(&actions[1])));
+        let firsts = (0..1000)
+            .filter(|_| policy.sample_action(&states[1], &mut rng) == Some(&actions[0]))
+            .count();
+        assert!((400..600).contains(&firsts));
+
+        // invalid probabilities and unknown states have no action
+        assert_eq!(policy.sample_action(&states[2], &mut rng), None);
+        assert_eq!(policy.sample_action(&S { id: 3 }, &mut rng), None);
+    }
+
+    #[test]
+    fn deterministic_mode() {
+        let states: Vec<S> = (0..3).map(|id| S { id }).collect();
+        let actions: Vec<A> = (0..3).map(|id| A { id }).collect();
+
+        let policy = StochasticPolicy::new(HashMap::from([
+            (&states[0], vec![(&actions[0], 0.2), (&actions[1], 0.8)]),
+            (
+                &states[1],
+                vec![(&actions[2], 0.4), (&actions[1], 0.4), (&actions[0], 0.2)],
+            ),
+            (&states[2], vec![]),
+        ]));
+        let deterministic = policy.to_deterministic();
+
+        // ties are broken by the lowest action ID and states without actions are dropped
+        assert_eq!(deterministic.select_action(&states[0]), Some(&actions[1]));
+        assert_eq!(deterministic.select_action(&states[1]), Some(&actions[1]));
+        assert_eq!(deterministic.select_action(&states[2]), None);
+
+        // converting back and forth preserves a deterministic policy
+        assert_eq!(
+            StochasticPolicy::from(deterministic).to_deterministic(),
+            Policy::new(HashMap::from([
+                (&states[0], &actions[1]),
+                (&states[1], &actions[1])
+            ]))
+        );
+    }
+
+    #[test]
+    fn epsilon_greedy_policy() {
+        let states: Vec<S> = (0..2).map(|id| S { id }).collect();
+        let actions: Vec<A> = (0..4).map(|id| A { id }).collect();
+        let base = || Policy::new(HashMap::from([(&states[0], &actions[2])]));
+        let mut rng = StdRng::seed_from_u64(7);
+
+        // without exploration the base action is always taken
+        let greedy = EpsilonGreedyPolicy::new(base(), 0.0, &actions);
+        assert!((0..100).all(|_| greedy.select_action(&states[0], &mut rng) == Some(&actions[2])));
+        assert_eq!(greedy.select_action(&states[1], &mut rng), None);
+
+        // with full exploration every action is taken
+        let random = EpsilonGreedyPolicy::new(base(), 1.0, &actions);
+        let mut counts = [0; 4];
+        for _ in 0..1000 {
+            counts[random.select_action(&states[0], &mut rng).unwrap().id] += 1;
+        }
+        assert!(counts.iter().all(|&count| count > 200));
+
+        let stochastic = StochasticPolicy::from(EpsilonGreedyPolicy::new(base(), 0.4, &actions));
+        assert!((stochastic.action_probability(&states[0], &actions[2]) - 0.7).abs() < 1e-12);
+        assert!((stochastic.action_probability(&states[0], &actions[0]) - 0.1).abs() < 1e-12);
+        assert_eq!(stochastic.action_probability(&states[1], &actions[0]), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid epsilon value: 1.5")]
+    fn invalid_epsilon() {
+        let actions = [A { id: 0 }];
+        EpsilonGreedyPolicy::<S, A>::new(Policy::new(HashMap::new()), 1.5, &actions);
+    }
 }